@@ -10,6 +10,11 @@ export SERVER_LISTEN_ADDR="127.0.0.1"
 export SERVER_LISTEN_PORT="8000"
 export SERVER_FRONTEND_ROOT="../frontend"
 export SERVER_SITES_ROOT="../sites"
+export SERVER_SITE_INDEX_PATH="../site_index.sqlite"
+
+# Set to "s3" and configure the S3_* variables to use a S3 compatible object store, i.e. MinIO
+# Only run a single instance with s3, site writes are locked within the process
+export STORAGE_BACKEND="filesystem"
 
 export SITE_KEY_MIN_LENGTH="20"
 export SITE_KEY_MAX_LENGTH="50"
@@ -20,9 +25,29 @@ export SITE_QUOTA_NANO_MAX_SIZE="1000000"
 export SITE_QUOTA_NANO_MAX_ROUTES="20"
 export SITE_QUOTA_NANO_MAX_SITES="10"
 
+export SITE_MAX_REVISIONS="10"
+
+# Cache-Control for files served from sites: no-cache, revalidate or max-age=<seconds>
+export SITE_CACHE_POLICY="no-cache"
+
+# In-memory caches of parsed sites and small files, set the max sizes to 0 to disable them
+export SITE_CACHE_MAX_SITES="1000"
+export SITE_CACHE_TTL="60"
+export BLOB_CACHE_MAX_SIZE="67108864"
+export BLOB_CACHE_MAX_FILE_SIZE="65536"
+
+# Bearer token for GET /json/admin/cache-stats, the admin routes are disabled when it is not set
+export SERVER_ADMIN_TOKEN="dev-admin-token"
+
+export BLOB_GC_INTERVAL="3600"
+export BLOB_GC_GRACE_PERIOD="3600"
+
 export COOKIE_SECURE="false"
 export COOKIE_MAX_AGE="315576000"
 
+# Seconds a browser can manage a site after the key was entered
+export SESSION_TTL="604800"
+
 export RUST_LOG=info
 
 cargo run
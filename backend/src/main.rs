@@ -1,7 +1,10 @@
 mod orri;
 
 use std::io;
+use std::env;
 use std::process;
+use std::thread;
+use std::time::Duration;
 use actix_web::{web, App, HttpServer};
 use actix_session::CookieSession;
 use actix_http::cookie::SameSite;
@@ -11,16 +14,23 @@ use orri::app_state::{self, AppState};
 use orri::http::index;
 use orri::http::guard;
 use orri::http::static_files;
+use orri::http::cache_stats;
 use orri::site::http::api as site_api;
 use orri::site::http as site_http;
 use orri::site_key;
 use orri::site;
+use orri::storage;
+use orri::session_store;
+use orri::command;
 use orri::route::Route;
 use orri::environment::{self, Environment};
 use crate::orri::http as http_helper;
 use log;
 
 
+const CACHE_STATS_LOG_INTERVAL: u64 = 300;
+const SESSION_CLEANUP_INTERVAL: u64 = 3600;
+
 
 fn app_domain_routes(config: &mut web::ServiceConfig, state: &AppState, host: &str) {
     let cookie_session = CookieSession::private(state.config.encryption_key.as_bytes())
@@ -65,6 +75,82 @@ fn app_domain_routes(config: &mut web::ServiceConfig, state: &AppState, host: &s
                 &Route::DeleteSiteJson().to_string(),
                 web::method(Route::DeleteSiteJson().request_method()).to(site_api::remove_site::handler)
             )
+            .route(
+                &Route::PutRouteJson("{domain}".to_string()).to_string(),
+                web::method(Route::PutRouteJson("{domain}".to_string()).request_method()).to(site_api::put_route::handler)
+            )
+            .route(
+                &Route::UploadRouteJson("{domain}".to_string()).to_string(),
+                web::method(Route::UploadRouteJson("{domain}".to_string()).request_method()).to(site_api::upload_route::handler)
+            )
+            .route(
+                &Route::DeploySiteJson("{domain}".to_string()).to_string(),
+                web::method(Route::DeploySiteJson("{domain}".to_string()).request_method()).to(site_api::deploy_site::handler)
+            )
+            .route(
+                &Route::ApplyChangesJson("{domain}".to_string()).to_string(),
+                web::method(Route::ApplyChangesJson("{domain}".to_string()).request_method()).to(site_api::apply_changes::handler)
+            )
+            .route(
+                &Route::ListRevisionsJson("{domain}".to_string()).to_string(),
+                web::method(Route::ListRevisionsJson("{domain}".to_string()).request_method()).to(site_api::list_revisions::handler)
+            )
+            .route(
+                &Route::DiffRevisionsJson("{domain}".to_string()).to_string(),
+                web::method(Route::DiffRevisionsJson("{domain}".to_string()).request_method()).to(site_api::diff_revisions::handler)
+            )
+            .route(
+                &Route::RollbackSiteJson("{domain}".to_string(), "{revision}".to_string()).to_string(),
+                web::method(Route::RollbackSiteJson("{domain}".to_string(), "{revision}".to_string()).request_method()).to(site_api::rollback_site::handler)
+            )
+            .route(
+                &Route::UpdateSettingsJson("{domain}".to_string()).to_string(),
+                web::method(Route::UpdateSettingsJson("{domain}".to_string()).request_method()).to(site_api::update_settings::handler)
+            )
+            .route(
+                &Route::AddRedirectJson("{domain}".to_string()).to_string(),
+                web::method(Route::AddRedirectJson("{domain}".to_string()).request_method()).to(site_api::add_redirect::handler)
+            )
+            .route(
+                &Route::EditRedirectJson("{domain}".to_string()).to_string(),
+                web::method(Route::EditRedirectJson("{domain}".to_string()).request_method()).to(site_api::edit_redirect::handler)
+            )
+            .route(
+                &Route::ListRoutesJson("{domain}".to_string()).to_string(),
+                web::method(Route::ListRoutesJson("{domain}".to_string()).request_method()).to(site_api::list_routes::handler)
+            )
+            .route(
+                &Route::RevokeSessionsJson("{domain}".to_string()).to_string(),
+                web::method(Route::RevokeSessionsJson("{domain}".to_string()).request_method()).to(site_api::revoke_sessions::handler)
+            )
+            .route(
+                &Route::AddSessionJson("{domain}".to_string()).to_string(),
+                web::method(Route::AddSessionJson("{domain}".to_string()).request_method()).to(site_api::add_session::handler)
+            )
+            .route(
+                &Route::CreateTokenJson("{domain}".to_string()).to_string(),
+                web::method(Route::CreateTokenJson("{domain}".to_string()).request_method()).to(site_api::create_token::handler)
+            )
+            .route(
+                &Route::RevokeTokenJson("{domain}".to_string(), "{id}".to_string()).to_string(),
+                web::method(Route::RevokeTokenJson("{domain}".to_string(), "{id}".to_string()).request_method()).to(site_api::revoke_token::handler)
+            )
+            .route(
+                &Route::AddCollaboratorJson("{domain}".to_string()).to_string(),
+                web::method(Route::AddCollaboratorJson("{domain}".to_string()).request_method()).to(site_api::add_collaborator::handler)
+            )
+            .route(
+                &Route::RemoveCollaboratorJson("{domain}".to_string(), "{id}".to_string()).to_string(),
+                web::method(Route::RemoveCollaboratorJson("{domain}".to_string(), "{id}".to_string()).request_method()).to(site_api::remove_collaborator::handler)
+            )
+            .route(
+                &Route::RotateKeyJson("{domain}".to_string()).to_string(),
+                web::method(Route::RotateKeyJson("{domain}".to_string()).request_method()).to(site_api::rotate_key::handler)
+            )
+            .route(
+                &Route::CacheStatsJson().to_string(),
+                web::method(Route::CacheStatsJson().request_method()).to(cache_stats::handler)
+            )
 
             // Static files
             .route("/static/{tail:.*}", web::get().to(static_files::handler))
@@ -98,6 +184,8 @@ fn build_server_config(env: &Environment) -> Result<app_state::ServerConfig, env
     let listen_port = environment::lookup(env, "SERVER_LISTEN_PORT")?;
     let frontend_root = environment::lookup(env, "SERVER_FRONTEND_ROOT")?;
     let sites_root = environment::lookup(env, "SERVER_SITES_ROOT")?;
+    let site_index_path = environment::lookup_optional(env, "SERVER_SITE_INDEX_PATH")?;
+    let admin_token = environment::lookup_optional(env, "SERVER_ADMIN_TOKEN")?;
 
     Ok(app_state::ServerConfig{
         environment,
@@ -108,6 +196,8 @@ fn build_server_config(env: &Environment) -> Result<app_state::ServerConfig, env
         listen_port,
         frontend_root,
         sites_root,
+        site_index_path,
+        admin_token,
     })
 }
 
@@ -147,21 +237,105 @@ fn build_cookie_config(env: &Environment) -> Result<app_state::CookieConfig, env
     })
 }
 
+fn build_s3_config(env: &Environment) -> Result<storage::s3::Config, environment::Error> {
+    let endpoint = environment::lookup(env, "S3_ENDPOINT")?;
+    let bucket = environment::lookup(env, "S3_BUCKET")?;
+    let region = environment::lookup(env, "S3_REGION")?;
+    let access_key = environment::lookup(env, "S3_ACCESS_KEY")?;
+    let secret_key = environment::lookup(env, "S3_SECRET_KEY")?;
+
+    Ok(storage::s3::Config{
+        endpoint,
+        bucket,
+        region,
+        access_key,
+        secret_key,
+    })
+}
+
+fn build_storage_config(env: &Environment) -> Result<storage::Config, environment::Error> {
+    let backend = environment::lookup_optional(env, "STORAGE_BACKEND")?.unwrap_or(storage::Backend::Filesystem());
+
+    match backend {
+        storage::Backend::Filesystem() =>
+            Ok(storage::Config::Filesystem()),
+
+        storage::Backend::S3() =>
+            Ok(storage::Config::S3(build_s3_config(env)?)),
+    }
+}
+
+fn build_blob_gc_config(env: &Environment) -> Result<site::gc::Config, environment::Error> {
+    let interval = environment::lookup_optional(env, "BLOB_GC_INTERVAL")?.unwrap_or(3600);
+    let grace_period = environment::lookup_optional(env, "BLOB_GC_GRACE_PERIOD")?.unwrap_or(3600);
+
+    Ok(site::gc::Config{
+        interval,
+        grace_period,
+    })
+}
+
+// The caches are disabled unless a max size is set
+fn build_site_cache(env: &Environment) -> Result<Option<site::cache::SiteCache>, environment::Error> {
+    let max_sites = environment::lookup_optional(env, "SITE_CACHE_MAX_SITES")?.unwrap_or(0);
+    let ttl = environment::lookup_optional(env, "SITE_CACHE_TTL")?.unwrap_or(60);
+
+    if max_sites == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(site::cache::SiteCache::new(&site::cache::Config{
+        max_sites,
+        ttl,
+    })))
+}
+
+fn build_blob_cache(env: &Environment) -> Result<Option<site::cache::BlobCache>, environment::Error> {
+    let max_size = environment::lookup_optional(env, "BLOB_CACHE_MAX_SIZE")?.unwrap_or(0);
+    let max_file_size = environment::lookup_optional(env, "BLOB_CACHE_MAX_FILE_SIZE")?.unwrap_or(65536);
+
+    if max_size == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(site::cache::BlobCache::new(&site::cache::BlobCacheConfig{
+        max_size,
+        max_file_size,
+    })))
+}
+
 fn build_config(env: &Environment) -> Result<app_state::Config, environment::Error> {
     let encryption_key = environment::lookup(env, "ENCRYPTION_KEY")?;
     let server = build_server_config(env)?;
     let cookie = build_cookie_config(env)?;
+    let session_ttl = environment::lookup_optional(env, "SESSION_TTL")?.unwrap_or(604800);
     let site_key = build_site_key_config(env)?;
     let quota_nano = build_site_quota_limits_nano(env)?;
+    let max_revisions = environment::lookup_optional(env, "SITE_MAX_REVISIONS")?.unwrap_or(10);
+    let blob_gc = build_blob_gc_config(env)?;
+    let storage = build_storage_config(env)?;
+    let site_cache_policy = environment::lookup_optional(env, "SITE_CACHE_POLICY")?.unwrap_or_default();
+    let cache = build_site_cache(env)?;
+    let blob_cache = build_blob_cache(env)?;
 
     Ok(app_state::Config{
         encryption_key,
         server,
         cookie,
+        session: session_store::Config{
+            ttl: session_ttl,
+        },
         site_key,
         site: site::Config{
             quota_nano,
-        }
+            max_revisions,
+            index: None,
+            cache,
+            blob_cache,
+        },
+        blob_gc,
+        storage,
+        site_cache_policy,
     })
 }
 
@@ -170,9 +344,17 @@ fn prepare_app_state() -> app_state::AppState {
     let env = environment::get_environment();
 
     match build_config(&env) {
-        Ok(config) => {
+        Ok(mut config) => {
+            let storage = storage::new(&config.storage, &config.server.sites_root);
+            let sessions = session_store::SessionStore::new(&storage, config.session.clone());
+
+            config.site.index = open_site_index(&config.server);
+
             app_state::AppState{
-                config
+                config,
+                site_locks: site::lock::SiteLocks::new(),
+                storage,
+                sessions,
             }
         },
 
@@ -183,13 +365,104 @@ fn prepare_app_state() -> app_state::AppState {
     }
 }
 
+fn open_site_index(server_config: &app_state::ServerConfig) -> Option<site::index::SiteIndex> {
+    let path = server_config.site_index_path.as_ref()?;
+
+    match site::index::SiteIndex::open(path) {
+        Ok(index) =>
+            Some(index),
+
+        Err(err) => {
+            log::error!("Failed to open site index: {}", err);
+            process::exit(1)
+        },
+    }
+}
+
+// Removes unreferenced blobs from the shared blob store at a fixed interval
+fn start_blob_gc(state: &AppState) {
+    let storage = state.storage.clone();
+    let config = state.config.blob_gc.clone();
+
+    thread::spawn(move || {
+        loop {
+            thread::sleep(Duration::from_secs(config.interval));
+
+            match site::gc::collect_garbage(&storage, &config) {
+                Ok(stats) =>
+                    log::info!("Blob gc finished, kept: {}, removed: {}", stats.kept, stats.removed),
+
+                Err(err) =>
+                    log::error!("Blob gc failed: {}", err),
+            }
+        }
+    });
+}
+
+// Removes sessions where every capability has expired
+fn start_session_cleanup(state: &AppState) {
+    let sessions = state.sessions.clone();
+
+    thread::spawn(move || {
+        loop {
+            thread::sleep(Duration::from_secs(SESSION_CLEANUP_INTERVAL));
+
+            match sessions.remove_expired() {
+                Ok(removed) =>
+                    log::info!("Session cleanup finished, removed: {}", removed),
+
+                Err(err) =>
+                    log::error!("Session cleanup failed: {}", err),
+            }
+        }
+    });
+}
+
+// Logs the cache hit and miss counters at a fixed interval, used to tune the cache sizes
+fn start_cache_stats_log(state: &AppState) {
+    let site_cache = state.config.site.cache.clone();
+    let blob_cache = state.config.site.blob_cache.clone();
+
+    if site_cache.is_none() && blob_cache.is_none() {
+        return;
+    }
+
+    thread::spawn(move || {
+        loop {
+            thread::sleep(Duration::from_secs(CACHE_STATS_LOG_INTERVAL));
+
+            if let Some(site_cache) = &site_cache {
+                log::info!("Site cache {}", site_cache.stats());
+            }
+
+            if let Some(blob_cache) = &blob_cache {
+                log::info!("Blob cache {}, size: {} bytes", blob_cache.stats(), blob_cache.size());
+            }
+        }
+    });
+}
+
 #[actix_rt::main]
 async fn main() -> Result<(), io::Error> {
     env_logger::init();
 
     let state = prepare_app_state();
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if !args.is_empty() {
+        return command::run(&state, &args)
+            .map_err(|err| {
+                log::error!("{}", err);
+                process::exit(1)
+            });
+    }
+
     let listen_addr = &state.config.server.listen_addr_with_port();
 
+    start_blob_gc(&state);
+    start_session_cleanup(&state);
+    start_cache_stats_log(&state);
+
     log::info!("Starting server on {}", listen_addr);
 
     HttpServer::new(move || {
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+use crate::orri::util;
+use crate::orri::encryption_key;
+use crate::orri::url_path::UrlPath;
+
+
+pub const MAX_TOKENS: usize = 20;
+pub const MAX_NAME_LENGTH: usize = 100;
+pub const MAX_EXPIRES_IN_DAYS: u64 = 3650;
+
+// Makes tokens recognizable in logs and secret scanners
+const TOKEN_PREFIX: &str = "orri_";
+
+
+// A credential for scripts and CI pipelines, only the sha256 of the token is stored.
+// The token itself has enough entropy that a slow hash like the site key's is not needed
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scope: Scope,
+    pub path_prefix: Option<UrlPath>,
+    pub expires: Option<u64>,
+    pub created: u64,
+    hash: String,
+}
+
+
+// Each scope includes the ones before it
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Read,
+    WriteRoutes,
+    DeleteSite,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scope::Read =>
+                write!(f, "read"),
+
+            Scope::WriteRoutes =>
+                write!(f, "write_routes"),
+
+            Scope::DeleteSite =>
+                write!(f, "delete_site"),
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Scope, Error> {
+        match s {
+            "read" =>
+                Ok(Scope::Read),
+
+            "write_routes" =>
+                Ok(Scope::WriteRoutes),
+
+            "delete_site" =>
+                Ok(Scope::DeleteSite),
+
+            _ =>
+                Err(Error::UnknownScope(s.to_string())),
+        }
+    }
+}
+
+
+// What a request needs to be allowed to do with a site
+pub enum Access<'a> {
+    Read(),
+
+    // Changes to the given routes only
+    WriteRoutes(Vec<&'a UrlPath>),
+
+    // Changes that replace every route or the settings of the site
+    WriteSite(),
+
+    DeleteSite(),
+
+    // Managing tokens and sessions always requires the site key
+    ManageSite(),
+}
+
+
+pub enum Error {
+    EmptyName(),
+    NameTooLong(),
+    UnknownScope(String),
+    InvalidExpiry(),
+    TooMany(),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyName() =>
+                write!(f, "The token needs a name"),
+
+            Error::NameTooLong() =>
+                write!(f, "The token name cannot be longer than {} characters", MAX_NAME_LENGTH),
+
+            Error::UnknownScope(scope) =>
+                write!(f, "Unknown scope «{}», expected read, write_routes or delete_site", scope),
+
+            Error::InvalidExpiry() =>
+                write!(f, "The token must expire within 1 to {} days", MAX_EXPIRES_IN_DAYS),
+
+            Error::TooMany() =>
+                write!(f, "A site cannot have more than {} tokens", MAX_TOKENS),
+        }
+    }
+}
+
+
+impl ApiToken {
+    // Returns the token and the secret that is shown to the user once
+    // Tokens without expires_in_days are valid until they are revoked
+    pub fn new(name: &str, scope: Scope, path_prefix: Option<UrlPath>, expires_in_days: Option<u64>, time: SystemTime) -> Result<(ApiToken, String), Error> {
+        let name = name.trim();
+
+        util::ensure(!name.is_empty(), Error::EmptyName())?;
+        util::ensure(name.chars().count() <= MAX_NAME_LENGTH, Error::NameTooLong())?;
+
+        let is_valid_expiry = expires_in_days
+            .map(|days| (1..=MAX_EXPIRES_IN_DAYS).contains(&days))
+            .unwrap_or(true);
+
+        util::ensure(is_valid_expiry, Error::InvalidExpiry())?;
+
+        let secret = format!("{}{}", TOKEN_PREFIX, encryption_key::random_string(40));
+
+        let token = ApiToken{
+            id: encryption_key::random_string(12),
+            name: name.to_string(),
+            scope,
+            path_prefix,
+            expires: expires_in_days.map(|days| util::unix_timestamp(time + Duration::from_secs(days * 24 * 60 * 60))),
+            created: util::unix_timestamp(time),
+            hash: util::sha256(secret.as_bytes()),
+        };
+
+        Ok((token, secret))
+    }
+
+    pub fn is_expired(&self, time: SystemTime) -> bool {
+        self.expires
+            .map(|expires| util::unix_timestamp(time) >= expires)
+            .unwrap_or(false)
+    }
+
+    pub fn allows(&self, access: &Access, time: SystemTime) -> bool {
+        if self.is_expired(time) {
+            return false;
+        }
+
+        match access {
+            Access::Read() =>
+                true,
+
+            Access::WriteRoutes(paths) =>
+                self.scope >= Scope::WriteRoutes && paths.iter().all(|path| self.has_path(path)),
+
+            Access::WriteSite() =>
+                self.scope >= Scope::WriteRoutes && self.path_prefix.is_none(),
+
+            Access::DeleteSite() =>
+                self.scope >= Scope::DeleteSite && self.path_prefix.is_none(),
+
+            Access::ManageSite() =>
+                false,
+        }
+    }
+
+    fn has_path(&self, path: &UrlPath) -> bool {
+        self.path_prefix
+            .as_ref()
+            .map(|prefix| is_under_prefix(&path.to_string(), &prefix.to_string()))
+            .unwrap_or(true)
+    }
+}
+
+
+// The prefix matches whole path segments, /docs matches /docs and /docs/intro but not /docs-old
+fn is_under_prefix(path: &str, prefix: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) =>
+            rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/'),
+
+        None =>
+            false,
+    }
+}
+
+
+pub fn find<'a>(tokens: &'a [ApiToken], secret: &str) -> Option<&'a ApiToken> {
+    let hash = util::sha256(secret.as_bytes());
+
+    tokens
+        .iter()
+        .find(|token| token.hash == hash)
+}
+
+pub fn is_allowed(tokens: &[ApiToken], secret: &str, access: &Access, time: SystemTime) -> bool {
+    find(tokens, secret)
+        .map(|token| token.allows(access, time))
+        .unwrap_or(false)
+}
@@ -3,11 +3,17 @@ use std::path::PathBuf;
 use crate::orri::encryption_key::EncryptionKey;
 use crate::orri::site_key;
 use crate::orri::site;
+use crate::orri::storage;
+use crate::orri::session_store::{self, SessionStore};
+use crate::orri::http::cache;
 use std::fmt;
 
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub config: Config,
+    pub site_locks: site::lock::SiteLocks,
+    pub storage: storage::Storage,
+    pub sessions: SessionStore,
 }
 
 
@@ -16,8 +22,12 @@ pub struct Config {
     pub encryption_key: EncryptionKey,
     pub server: ServerConfig,
     pub cookie: CookieConfig,
+    pub session: session_store::Config,
     pub site_key: site_key::Config,
     pub site: site::Config,
+    pub blob_gc: site::gc::Config,
+    pub storage: storage::Config,
+    pub site_cache_policy: cache::CachePolicy,
 }
 
 
@@ -68,6 +78,10 @@ pub struct ServerConfig {
     pub listen_port: u16,
     pub frontend_root: PathBuf,
     pub sites_root: PathBuf,
+    pub site_index_path: Option<PathBuf>,
+
+    // Bearer token for the admin json routes, they are disabled when it is not set
+    pub admin_token: Option<String>,
 }
 
 impl ServerConfig {
@@ -0,0 +1,120 @@
+use std::io::{self, Read, Seek, SeekFrom};
+use std::fs;
+use std::fmt;
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+
+enum Format {
+    Zip,
+    TarGz,
+}
+
+
+pub enum Error {
+    UnknownFormat(),
+    Read(io::Error),
+    Zip(ZipError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownFormat() =>
+                write!(f, "Unknown archive format, only zip and tar.gz is supported"),
+
+            Error::Read(err) =>
+                write!(f, "Failed to read archive: {}", err),
+
+            Error::Zip(err) =>
+                write!(f, "Failed to read zip archive: {}", err),
+        }
+    }
+}
+
+
+// Calls the given function with the name and content of every regular file in the archive
+pub fn for_each_file<F>(file: &mut fs::File, f: F) -> Result<(), Error>
+    where F: FnMut(&str, &mut dyn Read) {
+
+    let format = detect_format(file)?;
+
+    file.seek(SeekFrom::Start(0))
+        .map_err(Error::Read)?;
+
+    match format {
+        Format::Zip =>
+            for_each_zip_file(file, f),
+
+        Format::TarGz =>
+            for_each_tar_gz_file(file, f),
+    }
+}
+
+fn detect_format(file: &mut fs::File) -> Result<Format, Error> {
+    let mut magic = [0; 4];
+
+    file.read_exact(&mut magic)
+        .map_err(|_| Error::UnknownFormat())?;
+
+    match magic {
+        [0x50, 0x4b, 0x03, 0x04] =>
+            Ok(Format::Zip),
+
+        [0x1f, 0x8b, _, _] =>
+            Ok(Format::TarGz),
+
+        _ =>
+            Err(Error::UnknownFormat()),
+    }
+}
+
+fn for_each_zip_file<F>(file: &mut fs::File, mut f: F) -> Result<(), Error>
+    where F: FnMut(&str, &mut dyn Read) {
+
+    let mut archive = ZipArchive::new(file)
+        .map_err(Error::Zip)?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)
+            .map_err(Error::Zip)?;
+
+        if entry.is_dir() {
+            continue;
+        }
+
+        let name = entry.name().to_string();
+
+        f(&name, &mut entry);
+    }
+
+    Ok(())
+}
+
+fn for_each_tar_gz_file<F>(file: &mut fs::File, mut f: F) -> Result<(), Error>
+    where F: FnMut(&str, &mut dyn Read) {
+
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    let entries = archive.entries()
+        .map_err(Error::Read)?;
+
+    for entry_result in entries {
+        let mut entry = entry_result
+            .map_err(Error::Read)?;
+
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = entry.path()
+            .map_err(Error::Read)?
+            .to_string_lossy()
+            .to_string();
+
+        f(&name, &mut entry);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use std::io;
+use std::fs;
+use std::path::PathBuf;
+use std::collections::HashSet;
+use std::time::{SystemTime, Duration};
+use crate::orri::file;
+use crate::orri::compression::{self, Encoding};
+use crate::orri::storage::{Storage, ObjectInfo, ObjectReader};
+
+
+const BLOBS_PREFIX: &str = "blobs/";
+
+
+// Content addressed storage shared by all sites, a blob is stored once no matter how many
+// sites or revisions that reference it. Blobs are stored as blobs/aa/bb/<hash>, compressed
+// variants are stored next to the blob as <hash>.gz and <hash>.br
+#[derive(Clone)]
+pub struct BlobStore {
+    storage: Storage,
+}
+
+impl BlobStore {
+    pub fn new(storage: &Storage) -> BlobStore {
+        BlobStore{
+            storage: storage.clone(),
+        }
+    }
+
+    // Temp files are written here before they are stored,
+    // they are hidden files so they are never mistaken for blobs
+    pub fn temp_path(&self) -> PathBuf {
+        self.storage.temp_dir()
+    }
+
+    pub fn blob_key(&self, hash: &str) -> String {
+        let first = hash.get(0..2).unwrap_or("00");
+        let second = hash.get(2..4).unwrap_or("00");
+
+        format!("{}{}/{}/{}", BLOBS_PREFIX, first, second, hash)
+    }
+
+    pub fn variant_key(&self, hash: &str, encoding: Encoding) -> String {
+        format!("{}.{}", self.blob_key(hash), encoding.extension())
+    }
+
+    pub fn prepare_directories(&self) -> Result<(), io::Error> {
+        fs::create_dir_all(self.storage.temp_dir())
+    }
+
+    pub fn read(&self, hash: &str) -> Result<Vec<u8>, io::Error> {
+        self.storage.read(&self.blob_key(hash))
+    }
+
+    pub fn open(&self, hash: &str) -> Result<ObjectReader, io::Error> {
+        self.storage.open(&self.blob_key(hash))
+    }
+
+    pub fn open_range(&self, hash: &str, offset: u64, length: u64) -> Result<ObjectReader, io::Error> {
+        self.storage.open_range(&self.blob_key(hash), offset, length)
+    }
+
+    pub fn open_variant(&self, hash: &str, encoding: Encoding) -> Result<ObjectReader, io::Error> {
+        self.storage.open(&self.variant_key(hash, encoding))
+    }
+
+    // Variants that are not smaller than the original are not stored, those are served as is
+    pub fn write_variants(&self, hash: &str, data: &[u8]) -> Result<(), io::Error> {
+        for encoding in compression::ENCODINGS.iter() {
+            let compressed = compression::compress(*encoding, data)?;
+
+            if compressed.len() < data.len() {
+                self.storage.write(&self.variant_key(hash, *encoding), &compressed)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The blob is always rewritten, even if it exists, this updates the modified time
+    // which keeps the garbage collector from removing it before it's referenced
+    pub fn write(&self, hash: &str, data: &[u8]) -> Result<(), io::Error> {
+        self.storage.write(&self.blob_key(hash), data)
+    }
+
+    pub fn remove_object(&self, key: &str) -> Result<(), io::Error> {
+        self.storage.remove(key)
+    }
+
+    pub fn list(&self) -> Result<Vec<ObjectInfo>, io::Error> {
+        self.storage.list(BLOBS_PREFIX)
+    }
+
+    pub fn persist(&self, hashed_file: file::HashedFile) -> Result<(), io::Error> {
+        let key = self.blob_key(&hashed_file.hash);
+
+        self.storage.write_file(&key, hashed_file.into_temp_file())
+    }
+
+    // Removes blobs that are not referenced and has not been modified within the grace period.
+    // The grace period protects blobs that are written but not yet referenced by a persisted site.
+    // Sites are not locked while they are marked, so the modified time is read again right before
+    // a blob is removed, an upload may have rewritten it after it was listed
+    pub fn remove_unreferenced(&self, referenced: &HashSet<String>, grace_period: Duration) -> Result<RemoveStats, io::Error> {
+        let mut stats = RemoveStats::default();
+
+        for object in self.list()? {
+            let hash = hash_from_key(&object.key);
+
+            if referenced.contains(hash) || is_recent(object.modified, grace_period) {
+                stats.kept += 1;
+                continue;
+            }
+
+            match self.storage.modified(&object.key) {
+                Ok(modified) if !is_recent(modified, grace_period) =>
+                    (),
+
+                Ok(_) => {
+                    stats.kept += 1;
+                    continue;
+                },
+
+                Err(err) => {
+                    log::warn!("Failed to check blob {}: {}", hash, err);
+                    continue;
+                },
+            }
+
+            match self.storage.remove(&object.key) {
+                Ok(()) =>
+                    stats.removed += 1,
+
+                Err(err) =>
+                    log::warn!("Failed to remove blob {}: {}", hash, err),
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+
+fn is_recent(modified: SystemTime, grace_period: Duration) -> bool {
+    let age = SystemTime::now().duration_since(modified)
+        .unwrap_or_default();
+
+    age < grace_period
+}
+
+// Variants have the hash of the original blob, so they are kept and removed together with it
+pub fn hash_from_key(key: &str) -> &str {
+    key.rsplit('/').next()
+        .and_then(|name| name.split('.').next())
+        .unwrap_or_default()
+}
+
+
+#[derive(Default, Debug)]
+pub struct RemoveStats {
+    pub kept: usize,
+    pub removed: usize,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::orri::storage::filesystem::FilesystemStorage;
+
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn removes_only_unreferenced_blobs_outside_the_grace_period() {
+        let dir = tempfile::tempdir()
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        let storage: Storage = Arc::new(FilesystemStorage::new(dir.path()));
+        let blob_store = BlobStore::new(&storage);
+        assert!(blob_store.write(HASH, b"").is_ok());
+
+        let referenced = [HASH.to_string()].iter().cloned().collect();
+        let stats = blob_store.remove_unreferenced(&referenced, Duration::from_secs(0))
+            .unwrap_or_else(|err| panic!("{}", err));
+        assert_eq!((stats.kept, stats.removed), (1, 0));
+
+        let stats = blob_store.remove_unreferenced(&HashSet::new(), Duration::from_secs(3600))
+            .unwrap_or_else(|err| panic!("{}", err));
+        assert_eq!((stats.kept, stats.removed), (1, 0));
+
+        let stats = blob_store.remove_unreferenced(&HashSet::new(), Duration::from_secs(0))
+            .unwrap_or_else(|err| panic!("{}", err));
+        assert_eq!((stats.kept, stats.removed), (0, 1));
+        assert!(!storage.exists(&blob_store.blob_key(HASH)).unwrap_or(true));
+    }
+}
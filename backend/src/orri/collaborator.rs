@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+use crate::orri::util;
+use crate::orri::encryption_key;
+use crate::orri::site_key::{self, SiteKey};
+use crate::orri::api_token::Access;
+
+
+pub const MAX_COLLABORATORS: usize = 20;
+pub const MAX_NAME_LENGTH: usize = 100;
+
+// Collaborator keys are "<id>.<secret>", the id finds the collaborator so only one key hash is verified
+const KEY_SEPARATOR: char = '.';
+
+
+// A teammate with its own key, the site key is always an owner
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Collaborator {
+    pub id: String,
+    pub name: String,
+    pub role: Role,
+    pub created: u64,
+    key: SiteKey,
+}
+
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // Can do everything the site key can, including removing the site and managing keys
+    Owner,
+
+    // Can change routes, settings and deploy
+    Editor,
+
+    // Can see the revisions, collaborators and tokens of the site but change nothing
+    Viewer,
+}
+
+impl Role {
+    pub fn allows(&self, access: &Access) -> bool {
+        match (self, access) {
+            (Role::Owner, _) =>
+                true,
+
+            (Role::Editor, Access::DeleteSite()) | (Role::Editor, Access::ManageSite()) =>
+                false,
+
+            (Role::Editor, _) =>
+                true,
+
+            (Role::Viewer, Access::Read()) =>
+                true,
+
+            (Role::Viewer, _) =>
+                false,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Owner =>
+                write!(f, "owner"),
+
+            Role::Editor =>
+                write!(f, "editor"),
+
+            Role::Viewer =>
+                write!(f, "viewer"),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Role, Error> {
+        match s {
+            "owner" =>
+                Ok(Role::Owner),
+
+            "editor" =>
+                Ok(Role::Editor),
+
+            "viewer" =>
+                Ok(Role::Viewer),
+
+            _ =>
+                Err(Error::UnknownRole(s.to_string())),
+        }
+    }
+}
+
+
+pub enum Error {
+    EmptyName(),
+    NameTooLong(),
+    UnknownRole(String),
+    TooMany(),
+    SiteKey(site_key::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyName() =>
+                write!(f, "The collaborator needs a name"),
+
+            Error::NameTooLong() =>
+                write!(f, "The collaborator name cannot be longer than {} characters", MAX_NAME_LENGTH),
+
+            Error::UnknownRole(role) =>
+                write!(f, "Unknown role «{}», expected owner, editor or viewer", role),
+
+            Error::TooMany() =>
+                write!(f, "A site cannot have more than {} collaborators", MAX_COLLABORATORS),
+
+            Error::SiteKey(_) =>
+                write!(f, "Failed to create a key for the collaborator"),
+        }
+    }
+}
+
+
+impl Collaborator {
+    // Returns the collaborator and the generated key that is given to the teammate once
+    pub fn new(config: &site_key::Config, name: &str, role: Role, time: SystemTime) -> Result<(Collaborator, String), Error> {
+        let name = name.trim();
+
+        util::ensure(!name.is_empty(), Error::EmptyName())?;
+        util::ensure(name.chars().count() <= MAX_NAME_LENGTH, Error::NameTooLong())?;
+
+        let id = encryption_key::random_string(8);
+        let key = format!("{}{}{}", id, KEY_SEPARATOR, encryption_key::random_string(24));
+
+        let site_key = site_key::from_str(config, &key)
+            .map_err(Error::SiteKey)?;
+
+        let collaborator = Collaborator{
+            id,
+            name: name.to_string(),
+            role,
+            created: util::unix_timestamp(time),
+            key: site_key,
+        };
+
+        Ok((collaborator, key))
+    }
+
+    pub fn verify(&self, key: &str) -> Result<bool, site_key::VerifyError> {
+        self.key.verify(key)
+    }
+}
+
+
+// Returns the collaborator the key belongs to. A site key can look like a collaborator key,
+// so a key is only a collaborator's when the secret matches too, otherwise it's checked against the site key
+pub fn find_by_key<'a>(collaborators: &'a [Collaborator], key: &str) -> Result<Option<&'a Collaborator>, site_key::VerifyError> {
+    let id = match key.find(KEY_SEPARATOR) {
+        Some(index) =>
+            &key[..index],
+
+        None =>
+            return Ok(None),
+    };
+
+    match find(collaborators, id) {
+        Some(collaborator) =>
+            collaborator.verify(key)
+                .map(|is_valid| Some(collaborator).filter(|_| is_valid)),
+
+        None =>
+            Ok(None),
+    }
+}
+
+pub fn find<'a>(collaborators: &'a [Collaborator], id: &str) -> Option<&'a Collaborator> {
+    collaborators
+        .iter()
+        .find(|collaborator| collaborator.id == id)
+}
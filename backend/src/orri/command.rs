@@ -0,0 +1,218 @@
+use std::fmt;
+use std::time::Duration;
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, GetSiteError, MigrateSiteError, MigrateResult};
+use crate::orri::site::index::{self, SiteIndex};
+use crate::orri::site::fsck;
+
+
+// Maintenance commands, run as `orri <command> [args]` with the same environment as the server
+pub enum Error {
+    UnknownCommand(String),
+    SiteIndexNotConfigured(),
+    SiteIndex(index::Error),
+    ListSites(std::io::Error),
+    MigrationFailed(usize),
+    UnknownOption(String),
+    Fsck(fsck::Error),
+    SerializeReport(serde_json::Error),
+    FsckProblems(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownCommand(command) =>
+                write!(f, "Unknown command «{}», available commands: reindex, list-sites, migrate-all, fsck", command),
+
+            Error::SiteIndexNotConfigured() =>
+                write!(f, "The site index is not configured, set SERVER_SITE_INDEX_PATH"),
+
+            Error::SiteIndex(err) =>
+                write!(f, "{}", err),
+
+            Error::ListSites(err) =>
+                write!(f, "Failed to list sites: {}", err),
+
+            Error::MigrationFailed(count) =>
+                write!(f, "Failed to migrate {} sites", count),
+
+            Error::UnknownOption(option) =>
+                write!(f, "Unknown option «{}»", option),
+
+            Error::Fsck(err) =>
+                write!(f, "{}", err),
+
+            Error::SerializeReport(err) =>
+                write!(f, "Failed to serialize report: {}", err),
+
+            Error::FsckProblems(count) =>
+                write!(f, "Found {} problems that were not repaired", count),
+        }
+    }
+}
+
+
+pub fn run(state: &AppState, args: &[String]) -> Result<(), Error> {
+    let command = args.first()
+        .map(|arg| arg.as_str())
+        .unwrap_or_default();
+
+    match command {
+        "reindex" =>
+            reindex(state),
+
+        "list-sites" =>
+            list_sites(state, args.get(1).map(|arg| arg.as_str()).unwrap_or_default()),
+
+        "migrate-all" =>
+            migrate_all(state),
+
+        "fsck" =>
+            fsck(state, &args[1..]),
+
+        _ =>
+            Err(Error::UnknownCommand(command.to_string())),
+    }
+}
+
+
+fn get_site_index(state: &AppState) -> Result<&SiteIndex, Error> {
+    state.config.site.index.as_ref()
+        .ok_or(Error::SiteIndexNotConfigured())
+}
+
+fn reindex(state: &AppState) -> Result<(), Error> {
+    let site_index = get_site_index(state)?;
+
+    let stats = site_index.reindex(&state.storage)
+        .map_err(Error::SiteIndex)?;
+
+    println!("Indexed {} sites with a total size of {} bytes", stats.site_count, stats.total_size);
+
+    Ok(())
+}
+
+fn list_sites(state: &AppState, query: &str) -> Result<(), Error> {
+    let site_index = get_site_index(state)?;
+
+    let entries = site_index.list(query)
+        .map_err(Error::SiteIndex)?;
+
+    for entry in entries {
+        println!("{}\t{}\t{} routes\t{} bytes\tcreated {}\tupdated {}",
+            entry.domain, entry.quota, entry.route_count, entry.size, entry.created_at, entry.updated_at);
+    }
+
+    Ok(())
+}
+
+// Upgrades site.json of every site to the current schema version.
+// Failures are reported and the remaining sites are still migrated
+fn migrate_all(state: &AppState) -> Result<(), Error> {
+    let site_roots = site::list_site_roots(&state.storage)
+        .map_err(Error::ListSites)?;
+
+    let mut migrated = 0;
+    let mut up_to_date = 0;
+    let mut failed = 0;
+
+    for site_root in site_roots {
+        let result = state.site_locks.lock(&site_root)
+            .map_err(|err| format!("Failed to lock site: {}", err))
+            .and_then(|_lock| site::migrate(&site_root).map_err(format_migrate_error));
+
+        match result {
+            Ok(MigrateResult::Migrated(from_version)) => {
+                println!("{}\tmigrated from schema version {}", site_root.domain(), from_version);
+                migrated += 1;
+            },
+
+            Ok(MigrateResult::UpToDate()) =>
+                up_to_date += 1,
+
+            Ok(MigrateResult::SiteNotFound()) =>
+                (),
+
+            Err(err) => {
+                eprintln!("{}\t{}", site_root.domain(), err);
+                failed += 1;
+            },
+        }
+    }
+
+    println!("Migrated {} sites, {} already up to date, {} failed", migrated, up_to_date, failed);
+
+    if failed > 0 {
+        Err(Error::MigrationFailed(failed))
+    } else {
+        Ok(())
+    }
+}
+
+// Usage: fsck [--json] [--repair]
+fn fsck(state: &AppState, options: &[String]) -> Result<(), Error> {
+    let mut json = false;
+    let mut repair = false;
+
+    for option in options {
+        match option.as_str() {
+            "--json" =>
+                json = true,
+
+            "--repair" =>
+                repair = true,
+
+            _ =>
+                return Err(Error::UnknownOption(option.to_string())),
+        }
+    }
+
+    let fsck_options = fsck::Options{
+        repair,
+        grace_period: Duration::from_secs(state.config.blob_gc.grace_period),
+    };
+
+    let report = fsck::check(&state.storage, &state.site_locks, &state.config.site, &fsck_options)
+        .map_err(Error::Fsck)?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&report)
+            .map_err(Error::SerializeReport)?;
+
+        println!("{}", output);
+    } else {
+        for problem in &report.problems {
+            println!("{}", problem);
+        }
+
+        println!("Checked {} sites and {} files, found {} problems", report.site_count, report.blob_count, report.problems.len());
+    }
+
+    let unrepaired_count = report.unrepaired_count();
+
+    if unrepaired_count > 0 {
+        Err(Error::FsckProblems(unrepaired_count))
+    } else {
+        Ok(())
+    }
+}
+
+fn format_migrate_error(err: MigrateSiteError) -> String {
+    match err {
+        MigrateSiteError::GetSite(GetSiteError::SiteNotFound()) =>
+            "Site not found".to_string(),
+
+        MigrateSiteError::GetSite(GetSiteError::ReadSiteJson(err)) =>
+            format!("Failed to read site json: {}", err),
+
+        MigrateSiteError::GetSite(GetSiteError::MigrateSiteJson(err)) =>
+            format!("Failed to migrate site json: {}", err),
+
+        MigrateSiteError::WriteSiteJson(err) =>
+            format!("Failed to write site json: {}", err),
+
+        MigrateSiteError::MoveLegacyData(err) =>
+            format!("Failed to move legacy data to the blob store: {}", err),
+    }
+}
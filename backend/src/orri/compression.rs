@@ -0,0 +1,80 @@
+use std::io::{self, Write};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
+
+// Encodings that text files are precompressed with when they are stored
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    Brotli(),
+    Gzip(),
+}
+
+pub const ENCODINGS: [Encoding; 2] = [Encoding::Brotli(), Encoding::Gzip()];
+
+impl Encoding {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Encoding::Brotli() =>
+                "br",
+
+            Encoding::Gzip() =>
+                "gz",
+        }
+    }
+
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            Encoding::Brotli() =>
+                "br",
+
+            Encoding::Gzip() =>
+                "gzip",
+        }
+    }
+}
+
+
+pub fn compress(encoding: Encoding, data: &[u8]) -> Result<Vec<u8>, io::Error> {
+    match encoding {
+        Encoding::Brotli() => {
+            let mut writer = brotli::CompressorWriter::new(vec![], BROTLI_BUFFER_SIZE, BROTLI_QUALITY, BROTLI_WINDOW_SIZE);
+            writer.write_all(data)?;
+            Ok(writer.into_inner())
+        },
+
+        Encoding::Gzip() => {
+            let mut encoder = GzEncoder::new(vec![], Compression::best());
+            encoder.write_all(data)?;
+            encoder.finish()
+        },
+    }
+}
+
+
+// Text like files compress well, images, video and archives are already compressed
+pub fn is_compressible(mime: &str) -> bool {
+    let essence = mime
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || [
+            "application/javascript",
+            "application/json",
+            "application/xml",
+            "application/wasm",
+            "image/svg+xml",
+            "image/x-icon",
+        ].contains(&essence.as_str())
+}
@@ -27,6 +27,20 @@ pub fn lookup<T>(environment: &Environment, key: &'static str) -> Result<T, Erro
         })
 }
 
+// Returns None if the key is missing or the value is empty
+pub fn lookup_optional<T>(environment: &Environment, key: &'static str) -> Result<Option<T>, Error>
+    where T: FromStr,
+          T::Err: fmt::Display {
+
+    match environment.get(key) {
+        Some(string_value) if !string_value.is_empty() =>
+            lookup(environment, key).map(Some),
+
+        _ =>
+            Ok(None),
+    }
+}
+
 
 #[derive(Debug)]
 pub enum Error {
@@ -1,59 +1,66 @@
-use serde::{Serialize};
-use serde::de::DeserializeOwned;
 use serde_json;
 use std::io;
 use std::path::{Path};
-use std::fs::File;
-use std::io::{BufReader, Write};
+use std::io::Write;
 use std::fmt;
 use tempfile::NamedTempFile;
+use crate::orri::util;
 
 
-pub enum WriteError {
-    DetermineDir(),
-    CreateTempFile(io::Error),
-    WriteFile(io::Error),
-    Persist(io::Error),
+pub struct HashWriter {
+    file: NamedTempFile,
+    hasher: util::Sha256,
+    size: usize,
 }
 
-impl fmt::Display for WriteError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            WriteError::DetermineDir() =>
-                write!(f, "Invalid file path"),
+impl HashWriter {
+    pub fn new(dir: &Path) -> Result<HashWriter, io::Error> {
+        let file = NamedTempFile::new_in(dir)?;
 
-            WriteError::CreateTempFile(err) =>
-                write!(f, "Failed to create temp file: {}", err),
+        Ok(HashWriter{
+            file,
+            hasher: util::Sha256::new(),
+            size: 0,
+        })
+    }
 
-            WriteError::WriteFile(err) =>
-                write!(f, "Failed to write file: {}", err),
+    pub fn write(&mut self, data: &[u8]) -> Result<(), io::Error> {
+        self.file.write_all(data)?;
+        self.hasher.update(data);
+        self.size += data.len();
 
-            WriteError::Persist(err) =>
-                write!(f, "Failed to persist file: {}", err),
-        }
+        Ok(())
     }
-}
-
 
-pub fn write(path: &Path, data: &[u8]) -> Result<(), WriteError> {
-    let dir = path.parent()
-        .ok_or(WriteError::DetermineDir())?;
+    pub fn size(&self) -> usize {
+        self.size
+    }
 
-    let mut file = NamedTempFile::new_in(dir)
-        .map_err(WriteError::CreateTempFile)?;
+    pub fn finish(self) -> HashedFile {
+        HashedFile{
+            hash: self.hasher.finish(),
+            size: self.size,
+            file: self.file,
+        }
+    }
+}
 
-    file.write_all(data)
-        .map_err(WriteError::WriteFile)?;
 
-    file.persist(path)
-        .map_err(|err| WriteError::Persist(err.error))?;
+// A temp file with a known size and sha256 hash, it is deleted on drop unless stored
+pub struct HashedFile {
+    pub hash: String,
+    pub size: usize,
+    file: NamedTempFile,
+}
 
-    Ok(())
+impl HashedFile {
+    pub fn into_temp_file(self) -> NamedTempFile {
+        self.file
+    }
 }
 
+
 pub enum WriteJsonError {
-    DetermineDir(),
-    CreateTempFile(io::Error),
     Serialize(serde_json::error::Error),
     Persist(io::Error),
 }
@@ -61,12 +68,6 @@ pub enum WriteJsonError {
 impl fmt::Display for WriteJsonError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            WriteJsonError::DetermineDir() =>
-                write!(f, "Invalid file path"),
-
-            WriteJsonError::CreateTempFile(err) =>
-                write!(f, "Failed to create temp file: {}", err),
-
             WriteJsonError::Serialize(err) =>
                 write!(f, "Failed to serialize config: {}", err),
 
@@ -77,24 +78,6 @@ impl fmt::Display for WriteJsonError {
 }
 
 
-pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), WriteJsonError> {
-    let dir = path.parent()
-        .ok_or(WriteJsonError::DetermineDir())?;
-
-    let file = NamedTempFile::new_in(dir)
-        .map_err(WriteJsonError::CreateTempFile)?;
-
-    serde_json::to_writer_pretty(&file, value)
-        .map_err(WriteJsonError::Serialize)?;
-
-    file.persist(path)
-        .map_err(|err| WriteJsonError::Persist(err.error))?;
-
-    Ok(())
-}
-
-
-
 pub enum ReadJsonError {
     Open(io::Error),
     Deserialize(serde_json::error::Error),
@@ -111,13 +94,3 @@ impl fmt::Display for ReadJsonError {
         }
     }
 }
-
-pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ReadJsonError> {
-    let file = File::open(path)
-        .map_err(ReadJsonError::Open)?;
-
-    let reader = BufReader::new(file);
-
-    serde_json::from_reader(reader)
-        .map_err(ReadJsonError::Deserialize)
-}
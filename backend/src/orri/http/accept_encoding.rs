@@ -0,0 +1,101 @@
+use actix_http::http::HeaderMap;
+use http::header;
+use crate::orri::compression::Encoding;
+
+
+// Returns the supported encoding with the highest q-value, brotli wins a tie.
+// None means the client should get the file as is
+pub fn preferred(headers: &HeaderMap) -> Option<Encoding> {
+    let value = headers.get(header::ACCEPT_ENCODING)?
+        .to_str()
+        .ok()?;
+
+    // Q-values have at most three decimals, so they are compared as thousandths
+    let mut preferred: Option<(Encoding, u32)> = None;
+
+    for item in value.split(',') {
+        let mut parts = item.split(';');
+
+        let name = parts.next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+
+        let quality = parts
+            .map(|param| param.trim())
+            .filter(|param| param.starts_with("q="))
+            .filter_map(|param| param["q=".len()..].parse::<f32>().ok())
+            .next()
+            .map(|quality| (quality.clamp(0.0, 1.0) * 1000.0).round() as u32)
+            .unwrap_or(1000);
+
+        let encoding = match name.as_str() {
+            "br" =>
+                Encoding::Brotli(),
+
+            "gzip" | "x-gzip" =>
+                Encoding::Gzip(),
+
+            _ =>
+                continue,
+        };
+
+        if quality == 0 {
+            continue;
+        }
+
+        let is_better = preferred
+            .map(|(current, current_quality)| {
+                quality > current_quality || (quality == current_quality && current == Encoding::Gzip())
+            })
+            .unwrap_or(true);
+
+        if is_better {
+            preferred = Some((encoding, quality));
+        }
+    }
+
+    preferred.map(|(encoding, _)| encoding)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::http::HeaderValue;
+
+    fn preferred_for(value: &'static str) -> Option<Encoding> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_static(value));
+
+        preferred(&headers)
+    }
+
+    #[test]
+    fn prefers_brotli_on_a_tie() {
+        assert_eq!(preferred_for("gzip, deflate, br"), Some(Encoding::Brotli()));
+        assert_eq!(preferred_for("br;q=0.5, gzip;q=0.5"), Some(Encoding::Brotli()));
+    }
+
+    #[test]
+    fn prefers_the_highest_quality() {
+        assert_eq!(preferred_for("br;q=0.4, gzip;q=0.8"), Some(Encoding::Gzip()));
+        assert_eq!(preferred_for("x-gzip, br;q=0.999"), Some(Encoding::Gzip()));
+    }
+
+    #[test]
+    fn skips_encodings_with_zero_quality() {
+        assert_eq!(preferred_for("br;q=0, gzip"), Some(Encoding::Gzip()));
+        assert_eq!(preferred_for("br;q=0.0, gzip; q=0"), None);
+        assert_eq!(preferred_for("gzip;q=-1"), None);
+    }
+
+    #[test]
+    fn ignores_unsupported_and_malformed_values() {
+        assert_eq!(preferred_for("identity, deflate"), None);
+        assert_eq!(preferred_for(""), None);
+        assert_eq!(preferred_for("gzip;q=abc"), Some(Encoding::Gzip()));
+        assert_eq!(preferred_for("GZIP"), Some(Encoding::Gzip()));
+        assert_eq!(preferred(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,138 @@
+use actix_http::http::HeaderMap;
+use actix_web::dev::HttpResponseBuilder;
+use actix_http::http::header::HttpDate;
+use http::header;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH, Duration};
+use crate::orri::http as http_helper;
+use crate::orri::compression::Encoding;
+
+
+// Cache-Control policy for files served from sites, set with SITE_CACHE_POLICY
+#[derive(Clone, Debug)]
+pub enum CachePolicy {
+    // Nothing is cached, this is the default
+    NoCache(),
+
+    // Browsers may store files but must revalidate them with the ETag on every request
+    Revalidate(),
+
+    // Browsers may use stored files for the given number of seconds without asking the server
+    MaxAge(u64),
+}
+
+impl Default for CachePolicy {
+    fn default() -> CachePolicy {
+        CachePolicy::NoCache()
+    }
+}
+
+
+pub enum CachePolicyFromStrError {
+    UnknownPolicy(String),
+    InvalidMaxAge(String),
+}
+
+impl fmt::Display for CachePolicyFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CachePolicyFromStrError::UnknownPolicy(s) =>
+                write!(f, "Unknown cache policy «{0}», expected no-cache, revalidate or max-age=<seconds>", s),
+
+            CachePolicyFromStrError::InvalidMaxAge(s) =>
+                write!(f, "Invalid max-age «{0}»", s),
+        }
+    }
+}
+
+impl FromStr for CachePolicy {
+    type Err = CachePolicyFromStrError;
+
+    fn from_str(s: &str) -> Result<CachePolicy, CachePolicyFromStrError> {
+        match s {
+            "no-cache" =>
+                Ok(CachePolicy::NoCache()),
+
+            "revalidate" =>
+                Ok(CachePolicy::Revalidate()),
+
+            value if value.starts_with("max-age=") => {
+                let seconds = value.trim_start_matches("max-age=");
+
+                seconds.parse()
+                    .map(CachePolicy::MaxAge)
+                    .map_err(|_| CachePolicyFromStrError::InvalidMaxAge(seconds.to_string()))
+            },
+
+            value =>
+                Err(CachePolicyFromStrError::UnknownPolicy(value.to_string())),
+        }
+    }
+}
+
+
+// Validators for a file, the hash is the content hash so the etag is strong
+pub struct Validators {
+    pub etag: String,
+    pub last_modified: SystemTime,
+}
+
+impl Validators {
+    pub fn new(hash: &str, timestamp: u64) -> Validators {
+        Validators{
+            etag: format!("\"{}\"", hash),
+            last_modified: UNIX_EPOCH + Duration::from_secs(timestamp),
+        }
+    }
+
+    // Each encoding is a different representation of the file and needs its own strong etag
+    pub fn encoded(hash: &str, timestamp: u64, encoding: Encoding) -> Validators {
+        Validators{
+            etag: format!("\"{}.{}\"", hash, encoding.extension()),
+            last_modified: UNIX_EPOCH + Duration::from_secs(timestamp),
+        }
+    }
+}
+
+
+pub fn cache_headers<'a>(builder: &'a mut HttpResponseBuilder, policy: &CachePolicy, validators: &Validators) -> &'a mut HttpResponseBuilder {
+    let builder = match policy {
+        CachePolicy::NoCache() =>
+            http_helper::no_cache_headers(builder),
+
+        CachePolicy::Revalidate() =>
+            builder.set_header(header::CACHE_CONTROL, "no-cache"),
+
+        CachePolicy::MaxAge(seconds) =>
+            builder.set_header(header::CACHE_CONTROL, format!("public, max-age={}", seconds)),
+    };
+
+    builder
+        .set_header(header::ETAG, validators.etag.as_str())
+        .set_header(header::LAST_MODIFIED, HttpDate::from(validators.last_modified).to_string())
+}
+
+
+// If-None-Match takes precedence, If-Modified-Since is only used when it's missing
+pub fn is_not_modified(headers: &HeaderMap, validators: &Validators) -> bool {
+    if let Some(value) = headers.get(header::IF_NONE_MATCH) {
+        return value.to_str()
+            .map(|value| etag_matches(value, &validators.etag))
+            .unwrap_or(false);
+    }
+
+    headers.get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| HttpDate::from_str(value).ok())
+        .map(|date| validators.last_modified <= SystemTime::from(date))
+        .unwrap_or(false)
+}
+
+// If-None-Match uses the weak comparison, so W/ prefixes are ignored
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
@@ -0,0 +1,70 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Serialize;
+use ring::constant_time;
+use crate::orri::app_state::AppState;
+use crate::orri::site::cache::Stats;
+use crate::orri::util;
+use crate::orri::http as http_helper;
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    site_cache: Option<Stats>,
+    blob_cache: Option<Stats>,
+    blob_cache_size: Option<usize>,
+}
+
+enum Error {
+    AdminDisabled(),
+    NoTokenProvided(),
+    InvalidToken(),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
+
+    handle(&req, &state)
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(req: &HttpRequest, state: &AppState) -> Result<Response, Error> {
+    let admin_token = state.config.server.admin_token.as_ref()
+        .ok_or(Error::AdminDisabled())?;
+
+    let provided_token = http_helper::bearer_token(req)
+        .ok_or(Error::NoTokenProvided())?;
+
+    let is_valid = constant_time::verify_slices_are_equal(provided_token.as_bytes(), admin_token.as_bytes()).is_ok();
+    util::ensure(is_valid, Error::InvalidToken())?;
+
+    let site_cache = state.config.site.cache.as_ref();
+    let blob_cache = state.config.site.blob_cache.as_ref();
+
+    Ok(Response{
+        site_cache: site_cache.map(|cache| cache.stats()),
+        blob_cache: blob_cache.map(|cache| cache.stats()),
+        blob_cache_size: blob_cache.map(|cache| cache.size()),
+    })
+}
+
+fn prepare_response(response: Response) -> HttpResponse {
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(response)
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::AdminDisabled() =>
+            HttpResponse::NotFound()
+                .json(http_helper::Error::from_str("Not found")),
+
+        Error::NoTokenProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http_helper::Error::from_str("No token provided")),
+
+        Error::InvalidToken() =>
+            HttpResponse::Unauthorized()
+                .json(http_helper::Error::from_str("Invalid token")),
+    }
+}
@@ -1,13 +1,22 @@
 pub mod index;
 pub mod guard;
 pub mod static_files;
+pub mod cache;
+pub mod range;
+pub mod accept_encoding;
+pub mod stream_body;
+pub mod cache_stats;
 
 
 use serde::Serialize;
+use actix_web::{web, HttpRequest};
 use actix_web::dev::HttpResponseBuilder;
 use http::header;
 use actix_http::http as actix_http_helper;
 
+
+pub const SITE_KEY_HEADER: &str = "X-Site-Key";
+
 #[derive(Serialize)]
 pub struct Error {
     error: String,
@@ -43,3 +52,34 @@ pub fn get_host(headers: &actix_http_helper::header::HeaderMap) -> Host {
 
     Host(host)
 }
+
+
+// The token from an "Authorization: Bearer <token>" header
+pub fn bearer_token(req: &HttpRequest) -> Option<String> {
+    let value = req.headers().get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+
+    let mut parts = value.splitn(2, ' ');
+    let scheme = parts.next()?;
+    let token = parts.next()?.trim();
+
+    if scheme.eq_ignore_ascii_case("bearer") && !token.is_empty() {
+        Some(token.to_string())
+    } else {
+        None
+    }
+}
+
+
+// Runs work that waits for a site lock or does storage io on the thread pool, so the worker is not blocked.
+// Canceled is returned if the work panicked or the thread pool has shut down
+pub async fn block<F, T, E>(f: F, canceled: E) -> Result<T, E>
+    where F: FnOnce() -> Result<T, E> + Send + 'static,
+          T: Send + 'static,
+          E: Send + 'static {
+
+    web::block(move || Ok::<_, ()>(f()))
+        .await
+        .unwrap_or_else(|_| Err(canceled))
+}
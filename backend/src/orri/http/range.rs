@@ -0,0 +1,281 @@
+use actix_http::http::HeaderMap;
+use actix_http::http::header::HttpDate;
+use http::header;
+use std::str::FromStr;
+use std::time::SystemTime;
+use crate::orri::http::cache::Validators;
+
+
+// Requests with more ranges than this are rejected, a client has no good reason to ask for
+// lots of small pieces of the same file
+const MAX_RANGES: usize = 16;
+
+
+// A range of bytes where both start and end are inclusive
+#[derive(Clone, Copy, Debug)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    pub fn content_range(&self, size: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, size)
+    }
+}
+
+pub fn unsatisfied_content_range(size: u64) -> String {
+    format!("bytes */{}", size)
+}
+
+
+pub enum Error {
+    // Other units than bytes are ignored and the whole file is sent
+    UnknownUnit(),
+    Invalid(),
+    Unsatisfiable(),
+}
+
+
+// Returns the ranges to send, None means the whole file should be sent
+pub fn requested_ranges(headers: &HeaderMap, validators: &Validators, size: u64) -> Result<Option<Vec<ByteRange>>, Error> {
+    let value = match headers.get(header::RANGE) {
+        Some(value) =>
+            value.to_str().map_err(|_| Error::Invalid())?,
+
+        None =>
+            return Ok(None),
+    };
+
+    if !is_if_range_fresh(headers, validators) {
+        return Ok(None);
+    }
+
+    match parse(value, size) {
+        Ok(ranges) =>
+            Ok(Some(ranges)),
+
+        Err(Error::UnknownUnit()) =>
+            Ok(None),
+
+        Err(err) =>
+            Err(err),
+    }
+}
+
+// The range only applies if the file has not changed since the client got the first part of it.
+// An etag in If-Range must match with the strong comparison, a date must be exact
+fn is_if_range_fresh(headers: &HeaderMap, validators: &Validators) -> bool {
+    let value = match headers.get(header::IF_RANGE).map(|value| value.to_str()) {
+        Some(Ok(value)) =>
+            value.trim(),
+
+        Some(Err(_)) =>
+            return false,
+
+        None =>
+            return true,
+    };
+
+    if value.starts_with('"') || value.starts_with("W/") {
+        return value == validators.etag;
+    }
+
+    HttpDate::from_str(value)
+        .map(|date| SystemTime::from(date) == validators.last_modified)
+        .unwrap_or(false)
+}
+
+
+pub fn parse(value: &str, size: u64) -> Result<Vec<ByteRange>, Error> {
+    let value = value.trim();
+
+    if !value.starts_with("bytes=") {
+        return Err(Error::UnknownUnit());
+    }
+
+    let specs = value["bytes=".len()..]
+        .split(',')
+        .map(|spec| spec.trim())
+        .filter(|spec| !spec.is_empty())
+        .collect::<Vec<&str>>();
+
+    if specs.is_empty() || specs.len() > MAX_RANGES {
+        return Err(Error::Invalid());
+    }
+
+    let ranges = specs
+        .iter()
+        .map(|spec| parse_spec(spec, size))
+        .collect::<Result<Vec<Option<ByteRange>>, Error>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<ByteRange>>();
+
+    if ranges.is_empty() {
+        Err(Error::Unsatisfiable())
+    } else {
+        Ok(ranges)
+    }
+}
+
+// Returns None for a valid range that is outside of the file
+fn parse_spec(spec: &str, size: u64) -> Result<Option<ByteRange>, Error> {
+    let mut parts = spec.splitn(2, '-');
+
+    let first = parts.next()
+        .map(|part| part.trim())
+        .unwrap_or_default();
+
+    let last = parts.next()
+        .map(|part| part.trim())
+        .ok_or(Error::Invalid())?;
+
+    match (first, last) {
+        ("", "") =>
+            Err(Error::Invalid()),
+
+        // The last n bytes
+        ("", suffix_length) => {
+            let suffix_length = parse_position(suffix_length)?;
+
+            if suffix_length == 0 || size == 0 {
+                return Ok(None);
+            }
+
+            Ok(Some(ByteRange{
+                start: size.saturating_sub(suffix_length),
+                end: size - 1,
+            }))
+        },
+
+        // Everything from start to the end of the file
+        (start, "") => {
+            let start = parse_position(start)?;
+
+            if start >= size {
+                return Ok(None);
+            }
+
+            Ok(Some(ByteRange{
+                start,
+                end: size - 1,
+            }))
+        },
+
+        (start, end) => {
+            let start = parse_position(start)?;
+            let end = parse_position(end)?;
+
+            if end < start {
+                return Err(Error::Invalid());
+            }
+
+            if start >= size {
+                return Ok(None);
+            }
+
+            Ok(Some(ByteRange{
+                start,
+                end: end.min(size - 1),
+            }))
+        },
+    }
+}
+
+fn parse_position(s: &str) -> Result<u64, Error> {
+    if !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::Invalid());
+    }
+
+    u64::from_str(s)
+        .map_err(|_| Error::Invalid())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::http::HeaderValue;
+
+    fn parse_ranges(value: &str, size: u64) -> Result<Vec<(u64, u64)>, Error> {
+        parse(value, size)
+            .map(|ranges| ranges.iter().map(|range| (range.start, range.end)).collect())
+    }
+
+    fn is_invalid(result: Result<Vec<(u64, u64)>, Error>) -> bool {
+        matches!(result, Err(Error::Invalid()))
+    }
+
+    fn validators() -> Validators {
+        Validators{
+            etag: "\"abc\"".to_string(),
+            last_modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn parses_single_ranges() {
+        assert_eq!(parse_ranges("bytes=0-9", 100).ok(), Some(vec![(0, 9)]));
+        assert_eq!(parse_ranges("bytes=90-", 100).ok(), Some(vec![(90, 99)]));
+        assert_eq!(parse_ranges("bytes=90-200", 100).ok(), Some(vec![(90, 99)]));
+    }
+
+    #[test]
+    fn parses_suffix_ranges() {
+        assert_eq!(parse_ranges("bytes=-10", 100).ok(), Some(vec![(90, 99)]));
+        assert_eq!(parse_ranges("bytes=-500", 100).ok(), Some(vec![(0, 99)]));
+        assert!(matches!(parse_ranges("bytes=-0", 100), Err(Error::Unsatisfiable())));
+        assert!(matches!(parse_ranges("bytes=-10", 0), Err(Error::Unsatisfiable())));
+    }
+
+    #[test]
+    fn parses_multiple_ranges() {
+        assert_eq!(parse_ranges("bytes=0-9, 20-29,-5", 100).ok(), Some(vec![(0, 9), (20, 29), (95, 99)]));
+    }
+
+    #[test]
+    fn skips_ranges_outside_of_the_file() {
+        assert_eq!(parse_ranges("bytes=0-9,200-300", 100).ok(), Some(vec![(0, 9)]));
+        assert!(matches!(parse_ranges("bytes=100-", 100), Err(Error::Unsatisfiable())));
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        assert!(is_invalid(parse_ranges("bytes=", 100)));
+        assert!(is_invalid(parse_ranges("bytes=-", 100)));
+        assert!(is_invalid(parse_ranges("bytes=5", 100)));
+        assert!(is_invalid(parse_ranges("bytes=a-b", 100)));
+        assert!(is_invalid(parse_ranges("bytes=+1-5", 100)));
+        assert!(is_invalid(parse_ranges("bytes=9-0", 100)));
+        assert!(is_invalid(parse_ranges(&format!("bytes={}", vec!["0-1"; MAX_RANGES + 1].join(",")), 100)));
+    }
+
+    #[test]
+    fn ignores_unknown_units() {
+        assert!(matches!(parse_ranges("items=0-9", 100), Err(Error::UnknownUnit())));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, HeaderValue::from_static("items=0-9"));
+
+        assert!(matches!(requested_ranges(&headers, &validators(), 100), Ok(None)));
+    }
+
+    #[test]
+    fn sends_the_whole_file_when_if_range_does_not_match() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, HeaderValue::from_static("bytes=0-9"));
+        headers.insert(header::IF_RANGE, HeaderValue::from_static("\"other\""));
+
+        assert!(matches!(requested_ranges(&headers, &validators(), 100), Ok(None)));
+
+        headers.insert(header::IF_RANGE, HeaderValue::from_static("\"abc\""));
+
+        let ranges = requested_ranges(&headers, &validators(), 100).ok().flatten().map(|ranges| ranges.len());
+        assert_eq!(ranges, Some(1));
+    }
+}
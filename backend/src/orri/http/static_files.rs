@@ -2,6 +2,8 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use crate::orri::app_state::AppState;
 use crate::orri::url_path::{self, UrlPath};
 use crate::orri::http as http_helper;
+use crate::orri::http::stream_body;
+use crate::orri::util;
 use http::header;
 use std::io;
 use std::fs;
@@ -12,10 +14,12 @@ use mime_guess;
 enum Error {
     ParsePath(url_path::Error),
     ReadFile(io::Error),
+    NotAFile(),
 }
 
 struct File {
-    data: Vec<u8>,
+    file: fs::File,
+    size: u64,
     mime: String,
 }
 
@@ -34,13 +38,20 @@ fn handle(req: &HttpRequest, state: &AppState) -> Result<File, Error> {
 
     let path = state.config.server.frontend_root.join(url_path.relative_path());
 
-    let data = fs::read(&path)
+    let file = fs::File::open(&path)
         .map_err(Error::ReadFile)?;
 
+    let metadata = file.metadata()
+        .map_err(Error::ReadFile)?;
+
+    // Directories can be opened as files but not read
+    util::ensure(metadata.is_file(), Error::NotAFile())?;
+
     let mime = mime_guess::from_path(&path).first_or_octet_stream();
 
     Ok(File{
-        data,
+        file,
+        size: metadata.len(),
         mime: mime.to_string(),
     })
 }
@@ -48,7 +59,7 @@ fn handle(req: &HttpRequest, state: &AppState) -> Result<File, Error> {
 fn prepare_response(file: File) -> HttpResponse {
     http_helper::no_cache_headers(&mut HttpResponse::Ok())
         .set_header(header::CONTENT_TYPE, file.mime)
-        .body(file.data)
+        .body(stream_body::sized(Box::new(file.file), file.size))
 }
 
 fn handle_error(err: Error) -> HttpResponse {
@@ -61,5 +72,9 @@ fn handle_error(err: Error) -> HttpResponse {
             log::error!("Failed to read file: {}", err);
             HttpResponse::NotFound().finish()
         },
+
+        Error::NotAFile() => {
+            HttpResponse::NotFound().finish()
+        },
     }
 }
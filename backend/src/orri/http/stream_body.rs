@@ -0,0 +1,95 @@
+use actix_web::{web, Error};
+use actix_web::web::Bytes;
+use actix_web::error::BlockingError;
+use actix_http::body::{Body, SizedStream};
+use futures::Stream;
+use futures::future::{Future, FutureExt, LocalBoxFuture};
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+
+const CHUNK_SIZE: u64 = 65536;
+
+
+type ReadChunkResult = Result<(Box<dyn Read + Send>, Bytes), BlockingError<io::Error>>;
+
+
+// A response body with a known Content-Length that is read in chunks. The blocking reads run
+// on the thread pool, so only one chunk per response is kept in memory and the worker is never blocked.
+// Files on the local filesystem go through here as well, actix-http 1.0 writes every body through its
+// own buffers and has no sendfile path. The actix-files NamedFile of actix-web 2 reads chunks on the
+// thread pool the same way, so it would add a dependency without saving a copy
+pub fn sized(reader: Box<dyn Read + Send>, size: u64) -> Body {
+    Body::from_message(SizedStream::new(size, ChunkedReader::new(reader, size)))
+}
+
+
+struct ChunkedReader {
+    reader: Option<Box<dyn Read + Send>>,
+    remaining: u64,
+    read_chunk: Option<LocalBoxFuture<'static, ReadChunkResult>>,
+}
+
+impl ChunkedReader {
+    fn new(reader: Box<dyn Read + Send>, size: u64) -> ChunkedReader {
+        ChunkedReader{
+            reader: Some(reader),
+            remaining: size,
+            read_chunk: None,
+        }
+    }
+}
+
+impl Stream for ChunkedReader {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        if let Some(ref mut read_chunk) = self.read_chunk {
+            return match Pin::new(read_chunk).poll(cx) {
+                Poll::Ready(Ok((reader, bytes))) => {
+                    self.read_chunk = None;
+                    self.reader = Some(reader);
+                    self.remaining -= bytes.len() as u64;
+                    Poll::Ready(Some(Ok(bytes)))
+                },
+
+                Poll::Ready(Err(err)) =>
+                    Poll::Ready(Some(Err(err.into()))),
+
+                Poll::Pending =>
+                    Poll::Pending,
+            };
+        }
+
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        let mut reader = match self.reader.take() {
+            Some(reader) =>
+                reader,
+
+            None =>
+                return Poll::Ready(None),
+        };
+
+        let chunk_size = self.remaining.min(CHUNK_SIZE);
+
+        let read_chunk = web::block(move || {
+            let mut data = Vec::with_capacity(chunk_size as usize);
+
+            (&mut reader).take(chunk_size).read_to_end(&mut data)?;
+
+            // The body is shorter than the content length that was sent
+            if data.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "The file ended before the expected size"));
+            }
+
+            Ok((reader, Bytes::from(data)))
+        });
+
+        self.read_chunk = Some(read_chunk.boxed_local());
+        self.poll_next(cx)
+    }
+}
@@ -9,6 +9,15 @@ pub mod slowhtml;
 pub mod page;
 pub mod encryption_key;
 pub mod site_key;
+pub mod api_token;
+pub mod collaborator;
 pub mod session_data;
 pub mod route;
 pub mod environment;
+pub mod archive;
+pub mod blob_store;
+pub mod storage;
+pub mod command;
+pub mod compression;
+pub mod redirect;
+pub mod session_store;
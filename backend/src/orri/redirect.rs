@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use actix_http::http::StatusCode;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use crate::orri::util;
+
+
+// Where a redirect route points to, either a path on the same site or an absolute http(s) url
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Target(String);
+
+
+#[derive(Debug)]
+pub enum TargetError {
+    Empty(),
+    TooLong(),
+    ContainsDisallowedChars(),
+    MustBePathOrUrl(),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Target {
+    type Err = TargetError;
+
+    fn from_str(s: &str) -> Result<Self, TargetError> {
+        util::ensure(!s.is_empty(), TargetError::Empty())?;
+        util::ensure(s.len() < 2000, TargetError::TooLong())?;
+
+        // Whitespace and control characters are not allowed since the target is sent as is in the Location header
+        util::ensure(s.chars().all(|c| c.is_ascii_graphic()), TargetError::ContainsDisallowedChars())?;
+
+        // A path starting with // would be a protocol relative url to another host
+        let is_path = s.starts_with('/') && !s.starts_with("//");
+        let is_url = s.starts_with("https://") || s.starts_with("http://");
+
+        util::ensure(is_path || is_url, TargetError::MustBePathOrUrl())?;
+
+        Ok(Target(s.to_string()))
+    }
+}
+
+
+// Stored as the numeric status code
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub enum Status {
+    MovedPermanently(),
+    Found(),
+    TemporaryRedirect(),
+    PermanentRedirect(),
+}
+
+impl Default for Status {
+    fn default() -> Status {
+        Status::MovedPermanently()
+    }
+}
+
+impl Status {
+    pub fn all() -> Vec<Status> {
+        vec![
+            Status::MovedPermanently(),
+            Status::Found(),
+            Status::TemporaryRedirect(),
+            Status::PermanentRedirect(),
+        ]
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Status::MovedPermanently() =>
+                StatusCode::MOVED_PERMANENTLY,
+
+            Status::Found() =>
+                StatusCode::FOUND,
+
+            Status::TemporaryRedirect() =>
+                StatusCode::TEMPORARY_REDIRECT,
+
+            Status::PermanentRedirect() =>
+                StatusCode::PERMANENT_REDIRECT,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.status_code().as_u16())
+    }
+}
+
+impl From<Status> for u16 {
+    fn from(status: Status) -> u16 {
+        status.status_code().as_u16()
+    }
+}
+
+
+#[derive(Debug)]
+pub enum StatusError {
+    Unsupported(u16),
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatusError::Unsupported(code) =>
+                write!(f, "Unsupported redirect status {}, expected 301, 302, 307 or 308", code),
+        }
+    }
+}
+
+impl TryFrom<u16> for Status {
+    type Error = StatusError;
+
+    fn try_from(code: u16) -> Result<Status, StatusError> {
+        match code {
+            301 =>
+                Ok(Status::MovedPermanently()),
+
+            302 =>
+                Ok(Status::Found()),
+
+            307 =>
+                Ok(Status::TemporaryRedirect()),
+
+            308 =>
+                Ok(Status::PermanentRedirect()),
+
+            _ =>
+                Err(StatusError::Unsupported(code)),
+        }
+    }
+}
@@ -19,6 +19,25 @@ pub enum Route {
     EditRouteJson(),
     DeleteRouteJson(),
     DeleteSiteJson(),
+    PutRouteJson(String),
+    UploadRouteJson(String),
+    DeploySiteJson(String),
+    ApplyChangesJson(String),
+    ListRevisionsJson(String),
+    DiffRevisionsJson(String),
+    RollbackSiteJson(String, String),
+    UpdateSettingsJson(String),
+    AddRedirectJson(String),
+    EditRedirectJson(String),
+    ListRoutesJson(String),
+    AddSessionJson(String),
+    RevokeSessionsJson(String),
+    CreateTokenJson(String),
+    RevokeTokenJson(String, String),
+    AddCollaboratorJson(String),
+    RemoveCollaboratorJson(String, String),
+    RotateKeyJson(String),
+    CacheStatsJson(),
 }
 
 impl Route {
@@ -62,6 +81,63 @@ impl Route {
 
             Route::DeleteSiteJson() =>
                 Method::DELETE,
+
+            Route::PutRouteJson(_) =>
+                Method::PUT,
+
+            Route::UploadRouteJson(_) =>
+                Method::POST,
+
+            Route::DeploySiteJson(_) =>
+                Method::PUT,
+
+            Route::ApplyChangesJson(_) =>
+                Method::POST,
+
+            Route::ListRevisionsJson(_) =>
+                Method::GET,
+
+            Route::DiffRevisionsJson(_) =>
+                Method::GET,
+
+            Route::RollbackSiteJson(_, _) =>
+                Method::POST,
+
+            Route::UpdateSettingsJson(_) =>
+                Method::PUT,
+
+            Route::AddRedirectJson(_) =>
+                Method::POST,
+
+            Route::EditRedirectJson(_) =>
+                Method::PUT,
+
+            Route::ListRoutesJson(_) =>
+                Method::GET,
+
+            Route::AddSessionJson(_) =>
+                Method::POST,
+
+            Route::RevokeSessionsJson(_) =>
+                Method::DELETE,
+
+            Route::CreateTokenJson(_) =>
+                Method::POST,
+
+            Route::RevokeTokenJson(_, _) =>
+                Method::DELETE,
+
+            Route::AddCollaboratorJson(_) =>
+                Method::POST,
+
+            Route::RemoveCollaboratorJson(_, _) =>
+                Method::DELETE,
+
+            Route::RotateKeyJson(_) =>
+                Method::PUT,
+
+            Route::CacheStatsJson() =>
+                Method::GET,
         }
     }
 }
@@ -113,6 +189,63 @@ impl fmt::Display for Route {
 
             Route::DeleteSiteJson() =>
                 write!(f, "/json/sites"),
+
+            Route::PutRouteJson(domain) =>
+                write!(f, "/json/sites/{}/routes", domain),
+
+            Route::UploadRouteJson(domain) =>
+                write!(f, "/json/sites/{}/routes", domain),
+
+            Route::DeploySiteJson(domain) =>
+                write!(f, "/json/sites/{}/deploy", domain),
+
+            Route::ApplyChangesJson(domain) =>
+                write!(f, "/json/sites/{}/changes", domain),
+
+            Route::ListRevisionsJson(domain) =>
+                write!(f, "/json/sites/{}/revisions", domain),
+
+            Route::DiffRevisionsJson(domain) =>
+                write!(f, "/json/sites/{}/revisions/diff", domain),
+
+            Route::RollbackSiteJson(domain, revision) =>
+                write!(f, "/json/sites/{}/revisions/{}/rollback", domain, revision),
+
+            Route::UpdateSettingsJson(domain) =>
+                write!(f, "/json/sites/{}/settings", domain),
+
+            Route::AddRedirectJson(domain) =>
+                write!(f, "/json/sites/{}/redirects", domain),
+
+            Route::EditRedirectJson(domain) =>
+                write!(f, "/json/sites/{}/redirects", domain),
+
+            Route::ListRoutesJson(domain) =>
+                write!(f, "/json/sites/{}/routes", domain),
+
+            Route::AddSessionJson(domain) =>
+                write!(f, "/json/sites/{}/sessions", domain),
+
+            Route::RevokeSessionsJson(domain) =>
+                write!(f, "/json/sites/{}/sessions", domain),
+
+            Route::CreateTokenJson(domain) =>
+                write!(f, "/json/sites/{}/tokens", domain),
+
+            Route::RevokeTokenJson(domain, id) =>
+                write!(f, "/json/sites/{}/tokens/{}", domain, id),
+
+            Route::AddCollaboratorJson(domain) =>
+                write!(f, "/json/sites/{}/collaborators", domain),
+
+            Route::RemoveCollaboratorJson(domain, id) =>
+                write!(f, "/json/sites/{}/collaborators/{}", domain, id),
+
+            Route::RotateKeyJson(domain) =>
+                write!(f, "/json/sites/{}/key", domain),
+
+            Route::CacheStatsJson() =>
+                write!(f, "/json/admin/cache-stats"),
         }
     }
 }
@@ -1,26 +1,77 @@
 use serde::{Deserialize, Serialize};
-use serde_json;
 use crate::orri::util;
 use crate::orri::domain::Domain;
+use crate::orri::encryption_key;
+use crate::orri::file::{ReadJsonError, WriteJsonError};
+use crate::orri::session_store::SessionStore;
 use crate::orri::site::{self, Site};
-use std::collections::BTreeMap;
+use crate::orri::site_key;
+use crate::orri::api_token::{self, Access};
+use crate::orri::collaborator;
 use actix_session::Session;
 use actix_web;
+use std::time::SystemTime;
 
 
+// Only a random session id is kept in the encrypted cookie,
+// the sites the session has access to are kept in the session store
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SessionData {
-    sites: BTreeMap<Domain, String>
+    id: String,
 }
 
-const SESSION_KEY_NAME: &str = "data";
-const MAX_COOKIE_SIZE: usize = 4096;
+// Cookies from before the session store used "data" and are ignored
+const SESSION_KEY_NAME: &str = "session";
+
+
+// What a client used to prove that it may change a site
+pub enum Credential {
+    // The site key or a collaborator key, which is limited by the role of the collaborator
+    Key(String),
+
+    // An api token from the Authorization header, limited to its scope and path prefix
+    Token(String),
+
+    // The session has a valid capability for the site, the key was verified when it was granted.
+    // Holds the collaborator id if the capability was granted with a collaborator key
+    Session(Option<String>),
+}
+
+impl Credential {
+    pub fn verify(&self, site: &Site, access: &Access) -> Result<bool, site_key::VerifyError> {
+        match self {
+            Credential::Key(key) =>
+                match collaborator::find_by_key(&site.collaborators, key)? {
+                    Some(collaborator) =>
+                        Ok(collaborator.role.allows(access)),
+
+                    None =>
+                        site.key.verify(key),
+                },
+
+            Credential::Token(token) =>
+                Ok(api_token::is_allowed(&site.tokens, token, access, SystemTime::now())),
+
+            Credential::Session(None) =>
+                Ok(true),
+
+            // The role is looked up every time so a changed or removed collaborator takes effect at once
+            Credential::Session(Some(id)) => {
+                let is_allowed = collaborator::find(&site.collaborators, id)
+                    .map(|collaborator| collaborator.role.allows(access))
+                    .unwrap_or(false);
+
+                Ok(is_allowed)
+            },
+        }
+    }
+}
 
 
 impl SessionData {
     pub fn new() -> SessionData {
         SessionData{
-            sites: BTreeMap::new(),
+            id: encryption_key::random_string(32),
         }
     }
 
@@ -33,39 +84,66 @@ impl SessionData {
         session.set(SESSION_KEY_NAME, self)
     }
 
-    pub fn list_sites(&self) -> Vec<&Domain> {
-        self.sites.keys().collect()
+    // Sites with an expired or revoked capability are not listed
+    pub fn list_sites(&self, store: &SessionStore) -> Vec<Domain> {
+        let stored_session = store.get(&self.id)
+            .unwrap_or_else(|err| {
+                log::error!("Failed to read session: {}", err);
+                Default::default()
+            });
+
+        stored_session.sites
+            .iter()
+            .filter(|(domain, capability)| store.is_valid(domain, capability))
+            .map(|(domain, _)| domain.clone())
+            .collect()
     }
 
-    pub fn add_site(&mut self, site: &Site, site_config: &site::Config, key: &str) -> Result<(), Error> {
+    // Must only be called after the key has been verified
+    pub fn add_site(&self, store: &SessionStore, site: &Site, site_config: &site::Config, key: &str) -> Result<(), Error> {
         let limits = site.quota.limits(site_config);
 
-        util::ensure(self.sites.len() < limits.max_sites, Error::QuotaMaxSites())?;
-        util::ensure(self.estimated_cookie_size() < MAX_COOKIE_SIZE, Error::SessionDataTooLarge())?;
+        let mut stored_session = store.get(&self.id)
+            .map_err(Error::ReadSession)?;
 
-        self.sites.insert(site.domain.clone(), key.to_string());
+        // Expired and revoked capabilities don't count toward the limit
+        stored_session.sites.retain(|domain, capability| store.is_valid(domain, capability));
 
-        Ok(())
-    }
+        util::ensure(stored_session.sites.len() < limits.max_sites || stored_session.sites.contains_key(&site.domain), Error::QuotaMaxSites())?;
 
-    pub fn remove_site(&mut self, domain: &Domain) {
-        self.sites.remove(domain);
+        let collaborator = collaborator::find_by_key(&site.collaborators, key)
+            .map_err(Error::VerifyKey)?
+            .map(|collaborator| collaborator.id.clone());
+
+        stored_session.sites.insert(site.domain.clone(), store.new_capability(&site.domain, SystemTime::now(), collaborator));
+
+        store.put(&self.id, &stored_session)
+            .map_err(Error::WriteSession)
     }
 
-    pub fn get_site_key(&self, domain: &Domain) -> Option<String> {
-        self.sites.get(domain)
-            .map(|s| s.to_string())
+    pub fn remove_site(&self, store: &SessionStore, domain: &Domain) -> Result<(), Error> {
+        let mut stored_session = store.get(&self.id)
+            .map_err(Error::ReadSession)?;
+
+        stored_session.sites.remove(domain);
+
+        store.put(&self.id, &stored_session)
+            .map_err(Error::WriteSession)
     }
 
-    // Estimate encrypted cookie size
-    fn estimated_cookie_size(&self) -> usize {
-        serde_json::to_string(self)
-            .unwrap_or_default()
-            .len() * 2
+    pub fn get_site_credential(&self, store: &SessionStore, domain: &Domain) -> Option<Credential> {
+        let stored_session = store.get(&self.id).ok()?;
+
+        stored_session.sites
+            .get(domain)
+            .filter(|capability| store.is_valid(domain, capability))
+            .map(|capability| Credential::Session(capability.collaborator.clone()))
     }
 }
 
 pub enum Error {
     QuotaMaxSites(),
-    SessionDataTooLarge(),
+    VerifyKey(site_key::VerifyError),
+    ReadSession(ReadJsonError),
+    WriteSession(WriteJsonError),
 }
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, Instant};
+use crate::orri::util;
+use crate::orri::domain::Domain;
+use crate::orri::file::{ReadJsonError, WriteJsonError};
+use crate::orri::storage::{self, Storage};
+
+
+// Sessions are stored next to the sites, the prefix is not a valid domain so it's never listed as a site.
+// Keys are sessions/<sha256 of session id>.json and sessions/revoked/<domain>.json
+const SESSIONS_PREFIX: &str = "sessions/";
+const REVOCATIONS_PREFIX: &str = "sessions/revoked/";
+
+// Revocations are read from storage at most this often per site. A revocation made by this process
+// takes effect at once, one made by another process sharing the storage within this many seconds
+const REVOCATION_CACHE_TTL: Duration = Duration::from_secs(5);
+
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    // Seconds a site can be managed from a session after the key was entered
+    pub ttl: u64,
+}
+
+
+#[derive(Clone, Debug)]
+pub struct SessionStore {
+    storage: Storage,
+    config: Config,
+    generations: GenerationCache,
+}
+
+#[derive(Clone, Default)]
+struct GenerationCache(Arc<Mutex<HashMap<Domain, CachedGeneration>>>);
+
+impl fmt::Debug for GenerationCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GenerationCache")
+    }
+}
+
+struct CachedGeneration {
+    generation: u64,
+    read_at: Instant,
+}
+
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StoredSession {
+    pub sites: BTreeMap<Domain, Capability>,
+}
+
+// Access to one site, granted when the site key or a collaborator key is verified
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Capability {
+    pub created: u64,
+    pub expires: u64,
+
+    // The revocation generation of the site when the capability was created
+    #[serde(default)]
+    pub generation: u64,
+
+    // The collaborator whose key was used, none for the site key
+    #[serde(default)]
+    pub collaborator: Option<String>,
+}
+
+// Every revocation of a site increments the generation, capabilities from an older generation are revoked.
+// A counter is used instead of a timestamp so access granted in the same second as a revocation is kept
+#[derive(Serialize, Deserialize)]
+struct Revocation {
+    // Revocations written before generations were introduced revoke every capability older than them
+    #[serde(default = "first_generation")]
+    generation: u64,
+}
+
+fn first_generation() -> u64 {
+    1
+}
+
+
+impl SessionStore {
+    pub fn new(storage: &Storage, config: Config) -> SessionStore {
+        SessionStore{
+            storage: storage.clone(),
+            config,
+            generations: GenerationCache::default(),
+        }
+    }
+
+    // A session that has not been stored yet is empty
+    pub fn get(&self, session_id: &str) -> Result<StoredSession, ReadJsonError> {
+        match storage::read_json(&self.storage, &session_key(session_id)) {
+            Err(ReadJsonError::Open(err)) if storage::is_not_found(&err) =>
+                Ok(StoredSession::default()),
+
+            res =>
+                res,
+        }
+    }
+
+    pub fn put(&self, session_id: &str, session: &StoredSession) -> Result<(), WriteJsonError> {
+        storage::write_json(&self.storage, &session_key(session_id), session)
+    }
+
+    pub fn new_capability(&self, domain: &Domain, time: SystemTime, collaborator: Option<String>) -> Capability {
+        Capability{
+            created: util::unix_timestamp(time),
+            expires: util::unix_timestamp(time + Duration::from_secs(self.config.ttl)),
+            generation: self.generation(domain),
+            collaborator,
+        }
+    }
+
+    pub fn is_valid(&self, domain: &Domain, capability: &Capability) -> bool {
+        let now = util::unix_timestamp(SystemTime::now());
+
+        now < capability.expires && capability.generation >= self.generation(domain)
+    }
+
+    // Logs out every session that has access to the site, the sessions are not touched
+    pub fn revoke_site(&self, domain: &Domain) -> Result<(), WriteJsonError> {
+        // The current generation is always read from storage so a revocation is never lost to a stale cache
+        let current = self.read_generation(domain)
+            .unwrap_or(0);
+
+        let revocation = Revocation{
+            generation: current + 1,
+        };
+
+        storage::write_json(&self.storage, &revocation_key(domain), &revocation)?;
+        self.cache_generation(domain, revocation.generation);
+
+        Ok(())
+    }
+
+    fn generation(&self, domain: &Domain) -> u64 {
+        let cached = self.cached_generations()
+            .get(domain)
+            .filter(|cached| cached.read_at.elapsed() < REVOCATION_CACHE_TTL)
+            .map(|cached| cached.generation);
+
+        if let Some(generation) = cached {
+            return generation;
+        }
+
+        match self.read_generation(domain) {
+            Ok(generation) => {
+                self.cache_generation(domain, generation);
+                generation
+            },
+
+            // Fail closed, a revocation that can't be read revokes everything
+            Err(err) => {
+                log::error!("Failed to read session revocation for {}: {}", domain, err);
+                u64::MAX
+            },
+        }
+    }
+
+    fn read_generation(&self, domain: &Domain) -> Result<u64, ReadJsonError> {
+        match storage::read_json::<Revocation>(&self.storage, &revocation_key(domain)) {
+            Ok(revocation) =>
+                Ok(revocation.generation),
+
+            Err(ReadJsonError::Open(err)) if storage::is_not_found(&err) =>
+                Ok(0),
+
+            Err(err) =>
+                Err(err),
+        }
+    }
+
+    fn cache_generation(&self, domain: &Domain, generation: u64) {
+        self.cached_generations().insert(domain.clone(), CachedGeneration{
+            generation,
+            read_at: Instant::now(),
+        });
+    }
+
+    fn cached_generations(&self) -> std::sync::MutexGuard<'_, HashMap<Domain, CachedGeneration>> {
+        self.generations.0.lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Every capability in a session that has not been written for longer than the ttl has expired.
+    // Revocations are kept, the generation would start over and let older capabilities through
+    pub fn remove_expired(&self) -> Result<usize, io::Error> {
+        let expired_before = SystemTime::now() - Duration::from_secs(self.config.ttl);
+
+        let expired = self.storage.list(SESSIONS_PREFIX)?
+            .into_iter()
+            .filter(|object| !object.key.starts_with(REVOCATIONS_PREFIX))
+            .filter(|object| object.modified < expired_before)
+            .collect::<Vec<storage::ObjectInfo>>();
+
+        for object in &expired {
+            self.storage.remove(&object.key)?;
+        }
+
+        Ok(expired.len())
+    }
+}
+
+// The session id is the secret kept in the cookie, only its hash is used as the key
+fn session_key(session_id: &str) -> String {
+    format!("{}{}.json", SESSIONS_PREFIX, util::sha256(session_id.as_bytes()))
+}
+
+fn revocation_key(domain: &Domain) -> String {
+    format!("{}{}.json", REVOCATIONS_PREFIX, domain)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::thread;
+    use crate::orri::storage::filesystem::FilesystemStorage;
+
+    fn new_store(storage: &Storage) -> SessionStore {
+        // Everything written before now is expired by remove_expired
+        SessionStore::new(storage, Config{ ttl: 0 })
+    }
+
+    fn unexpired_capability(store: &SessionStore, domain: &Domain) -> Capability {
+        Capability{
+            expires: util::unix_timestamp(SystemTime::now() + Duration::from_secs(3600)),
+            ..store.new_capability(domain, SystemTime::now(), None)
+        }
+    }
+
+    #[test]
+    fn revocations_survive_cleanup() {
+        let dir = tempfile::tempdir()
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        let storage: Storage = Arc::new(FilesystemStorage::new(dir.path()));
+        let domain = Domain::from_str("testsite.orri.dev")
+            .unwrap_or_else(|_| panic!("invalid domain"));
+
+        let store = new_store(&storage);
+        assert!(store.revoke_site(&domain).is_ok());
+
+        let capability = unexpired_capability(&store, &domain);
+        assert!(store.is_valid(&domain, &capability));
+
+        thread::sleep(Duration::from_millis(20));
+        assert!(store.remove_expired().is_ok());
+
+        // A new store reads the generation from storage instead of the cache
+        let store = new_store(&storage);
+        assert!(store.is_valid(&domain, &capability));
+        assert!(store.revoke_site(&domain).is_ok());
+
+        assert!(!new_store(&storage).is_valid(&domain, &capability));
+    }
+}
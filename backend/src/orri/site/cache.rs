@@ -0,0 +1,278 @@
+use std::io::{self, Read};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Instant, Duration};
+use lru::LruCache;
+use serde::Serialize;
+use actix_web::web::Bytes;
+use crate::orri::domain::Domain;
+use crate::orri::storage::ObjectReader;
+use crate::orri::site::Site;
+
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub max_sites: usize,
+    pub ttl: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct BlobCacheConfig {
+    pub max_size: usize,
+    pub max_file_size: usize,
+}
+
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Counters {
+    fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+
+#[derive(Serialize)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "hits: {}, misses: {}, entries: {}", self.hits, self.misses, self.entries)
+    }
+}
+
+
+// Parsed site documents for serving pages. Entries are removed when a site is persisted or removed,
+// the ttl limits how long a change made by another process, i.e. another server using the same S3 bucket, can go unnoticed
+#[derive(Clone)]
+pub struct SiteCache {
+    inner: Arc<SiteCacheInner>,
+}
+
+struct SiteCacheInner {
+    entries: Mutex<SiteEntries>,
+    ttl: Duration,
+    counters: Counters,
+}
+
+struct SiteEntries {
+    sites: LruCache<Domain, SiteEntry>,
+
+    // Incremented when an entry is removed, a site that was read before that is not inserted
+    // since it may be older than the change that removed the entry
+    generation: u64,
+}
+
+// Shared with the requests serving the site so a hit doesn't copy the routes and rules
+struct SiteEntry {
+    site: Arc<Site>,
+    inserted: Instant,
+}
+
+impl fmt::Debug for SiteCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SiteCache")
+    }
+}
+
+impl SiteCache {
+    pub fn new(config: &Config) -> SiteCache {
+        SiteCache{
+            inner: Arc::new(SiteCacheInner{
+                entries: Mutex::new(SiteEntries{
+                    sites: LruCache::new(config.max_sites),
+                    generation: 0,
+                }),
+                ttl: Duration::from_secs(config.ttl),
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, SiteEntries> {
+        self.inner.entries.lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub fn get_or_insert_with<E, F>(&self, domain: &Domain, read_site: F) -> Result<Arc<Site>, E>
+        where F: FnOnce() -> Result<Site, E> {
+
+        let generation = {
+            let mut entries = self.entries();
+            let generation = entries.generation;
+
+            if let Some(entry) = entries.sites.get(domain) {
+                if entry.inserted.elapsed() < self.inner.ttl {
+                    self.inner.counters.hit();
+                    return Ok(entry.site.clone());
+                }
+            }
+
+            generation
+        };
+
+        self.inner.counters.miss();
+
+        // The lock is not held while reading so other sites can be served in the meantime
+        let site = Arc::new(read_site()?);
+
+        let mut entries = self.entries();
+
+        if entries.generation == generation {
+            entries.sites.put(domain.clone(), SiteEntry{
+                site: site.clone(),
+                inserted: Instant::now(),
+            });
+        }
+
+        Ok(site)
+    }
+
+    pub fn invalidate(&self, domain: &Domain) {
+        let mut entries = self.entries();
+
+        entries.sites.pop(domain);
+        entries.generation += 1;
+    }
+
+    pub fn stats(&self) -> Stats {
+        let entries = self.entries();
+
+        Stats{
+            hits: self.inner.counters.hits.load(Ordering::Relaxed),
+            misses: self.inner.counters.misses.load(Ordering::Relaxed),
+            entries: entries.sites.len(),
+        }
+    }
+}
+
+
+// Small, frequently requested blobs kept in memory. Blobs are content addressed
+// so an entry never becomes stale and is only removed to make room for others
+#[derive(Clone)]
+pub struct BlobCache {
+    inner: Arc<BlobCacheInner>,
+}
+
+struct BlobCacheInner {
+    entries: Mutex<BlobEntries>,
+    max_size: usize,
+    max_file_size: usize,
+    counters: Counters,
+}
+
+struct BlobEntries {
+    blobs: LruCache<String, Bytes>,
+    size: usize,
+}
+
+impl fmt::Debug for BlobCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BlobCache")
+    }
+}
+
+impl BlobCache {
+    pub fn new(config: &BlobCacheConfig) -> BlobCache {
+        BlobCache{
+            inner: Arc::new(BlobCacheInner{
+                entries: Mutex::new(BlobEntries{
+                    blobs: LruCache::unbounded(),
+                    size: 0,
+                }),
+                max_size: config.max_size,
+                max_file_size: config.max_file_size,
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, BlobEntries> {
+        self.inner.entries.lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Objects larger than max_file_size are streamed from the storage as usual
+    pub fn open<F>(&self, key: &str, open_object: F) -> Result<ObjectReader, io::Error>
+        where F: FnOnce() -> Result<ObjectReader, io::Error> {
+
+        if let Some(data) = self.entries().blobs.get(&key.to_string()) {
+            self.inner.counters.hit();
+            return Ok(object_reader(data.clone()));
+        }
+
+        self.inner.counters.miss();
+
+        let object = open_object()?;
+
+        if object.size as usize > self.inner.max_file_size {
+            return Ok(object);
+        }
+
+        let mut data = Vec::with_capacity(object.size as usize);
+        let mut reader = object.reader;
+
+        reader.read_to_end(&mut data)?;
+
+        let data = Bytes::from(data);
+
+        self.insert(key, data.clone());
+
+        Ok(object_reader(data))
+    }
+
+    fn insert(&self, key: &str, data: Bytes) {
+        let mut entries = self.entries();
+
+        entries.size += data.len();
+
+        if let Some(old_data) = entries.blobs.put(key.to_string(), data) {
+            entries.size -= old_data.len();
+        }
+
+        while entries.size > self.inner.max_size {
+            match entries.blobs.pop_lru() {
+                Some((_key, data)) =>
+                    entries.size -= data.len(),
+
+                None =>
+                    break,
+            }
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        let entries = self.entries();
+
+        Stats{
+            hits: self.inner.counters.hits.load(Ordering::Relaxed),
+            misses: self.inner.counters.misses.load(Ordering::Relaxed),
+            entries: entries.blobs.len(),
+        }
+    }
+
+    // The total size in bytes of the cached blobs
+    pub fn size(&self) -> usize {
+        self.entries().size
+    }
+}
+
+fn object_reader(data: Bytes) -> ObjectReader {
+    ObjectReader{
+        size: data.len() as u64,
+        reader: Box::new(io::Cursor::new(data)),
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use crate::orri::util;
+use crate::orri::url_path::UrlPath;
+use crate::orri::site::{self, Site, Config, RouteInfo, FileInfo, File};
+
+
+// A batch of route changes that is validated and applied to a site as a whole
+pub struct SiteChangeSet {
+    operations: Vec<Operation>,
+}
+
+enum Operation {
+    Add(UrlPath, File),
+    Update(UrlPath, File),
+    Remove(UrlPath),
+    Rename(UrlPath, UrlPath),
+}
+
+
+pub enum ApplyChangeSetError {
+    RouteAlreadyExist(UrlPath),
+    RouteNotFound(UrlPath),
+    MissingRoot(),
+    QuotaMaxSize(),
+    QuotaMaxRoutes(),
+}
+
+
+impl SiteChangeSet {
+    pub fn new() -> SiteChangeSet {
+        SiteChangeSet{
+            operations: vec![],
+        }
+    }
+
+    pub fn add_route(&mut self, path: UrlPath, file_info: FileInfo, file_data: Vec<u8>) -> &mut SiteChangeSet {
+        self.operations.push(Operation::Add(path, File{
+            metadata: file_info,
+            data: file_data,
+        }));
+
+        self
+    }
+
+    pub fn update_route(&mut self, path: UrlPath, file_info: FileInfo, file_data: Vec<u8>) -> &mut SiteChangeSet {
+        self.operations.push(Operation::Update(path, File{
+            metadata: file_info,
+            data: file_data,
+        }));
+
+        self
+    }
+
+    pub fn remove_route(&mut self, path: UrlPath) -> &mut SiteChangeSet {
+        self.operations.push(Operation::Remove(path));
+
+        self
+    }
+
+    pub fn rename_route(&mut self, from: UrlPath, to: UrlPath) -> &mut SiteChangeSet {
+        self.operations.push(Operation::Rename(from, to));
+
+        self
+    }
+
+    // Every path that is changed, for checking the access of the credential
+    pub fn paths(&self) -> Vec<&UrlPath> {
+        self.operations
+            .iter()
+            .flat_map(|operation| match operation {
+                Operation::Add(path, _) =>
+                    vec![path],
+
+                Operation::Update(path, _) =>
+                    vec![path],
+
+                Operation::Remove(path) =>
+                    vec![path],
+
+                Operation::Rename(from, to) =>
+                    vec![from, to],
+            })
+            .collect()
+    }
+}
+
+
+impl Site {
+    // Applies all operations in order, the site is only changed if every operation succeeds
+    // and the resulting routes are within the quota limits
+    pub fn apply_change_set(&mut self, config: &Config, change_set: SiteChangeSet) -> Result<&Site, ApplyChangeSetError> {
+        let limits = self.quota.limits(config);
+        let mut routes = self.routes.clone();
+        let mut files = vec![];
+
+        for operation in change_set.operations {
+            apply_operation(&mut routes, &mut files, operation)?;
+        }
+
+        util::ensure(routes.contains_key(&UrlPath::root()), ApplyChangeSetError::MissingRoot())?;
+        util::ensure(site::routes_size(&routes) < limits.max_size, ApplyChangeSetError::QuotaMaxSize())?;
+        util::ensure(routes.len() <= limits.max_routes, ApplyChangeSetError::QuotaMaxRoutes())?;
+
+        self.routes = routes;
+        self.new_files.extend(files.iter().map(|file| file.metadata.clone()));
+        self.unwritten_files.extend(files);
+
+        Ok(self)
+    }
+}
+
+
+fn apply_operation(routes: &mut BTreeMap<UrlPath, RouteInfo>, files: &mut Vec<File>, operation: Operation) -> Result<(), ApplyChangeSetError> {
+    match operation {
+        Operation::Add(path, file) => {
+            util::ensure(!routes.contains_key(&path), ApplyChangeSetError::RouteAlreadyExist(path.clone()))?;
+
+            routes.insert(path, RouteInfo::File{
+                file_info: file.metadata.clone(),
+                headers: vec![],
+            });
+
+            files.push(file);
+        },
+
+        Operation::Update(path, file) => {
+            let headers = routes.get(&path)
+                .map(|route| route.headers().to_vec())
+                .ok_or(ApplyChangeSetError::RouteNotFound(path.clone()))?;
+
+            routes.insert(path, RouteInfo::File{
+                file_info: file.metadata.clone(),
+                headers,
+            });
+
+            files.push(file);
+        },
+
+        Operation::Remove(path) => {
+            routes.remove(&path)
+                .ok_or(ApplyChangeSetError::RouteNotFound(path))?;
+        },
+
+        Operation::Rename(from, to) => {
+            util::ensure(!routes.contains_key(&to), ApplyChangeSetError::RouteAlreadyExist(to.clone()))?;
+
+            let route_info = routes.remove(&from)
+                .ok_or(ApplyChangeSetError::RouteNotFound(from))?;
+
+            routes.insert(to, route_info);
+        },
+    }
+
+    Ok(())
+}
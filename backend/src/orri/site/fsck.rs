@@ -0,0 +1,435 @@
+use std::io;
+use std::fmt;
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, Duration};
+use serde::Serialize;
+use crate::orri::util;
+use crate::orri::file;
+use crate::orri::domain::Domain;
+use crate::orri::storage::{self, Storage};
+use crate::orri::blob_store::{self, BlobStore};
+use crate::orri::site::{self, Site, SiteRoot, Config, GetSiteError, RouteInfo};
+use crate::orri::site::lock::SiteLocks;
+use crate::orri::site::revision::{self, Revision, ListRevisionsError};
+use crate::orri::site::index;
+
+
+pub struct Options {
+    pub repair: bool,
+    pub grace_period: Duration,
+}
+
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub site_count: usize,
+    pub blob_count: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    pub fn unrepaired_count(&self) -> usize {
+        self.problems
+            .iter()
+            .filter(|problem| !problem.repaired)
+            .count()
+    }
+}
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Problem {
+    pub kind: ProblemKind,
+    pub key: String,
+    pub details: String,
+    pub repaired: bool,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let repaired = if self.repaired { " (repaired)" } else { "" };
+
+        write!(f, "{}\t{}\t{}{}", self.kind, self.key, self.details, repaired)
+    }
+}
+
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ProblemKind {
+    MissingFile,
+    CorruptFile,
+    SizeMismatch,
+    OrphanedFile,
+    UnreadableSiteJson,
+}
+
+impl fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProblemKind::MissingFile =>
+                write!(f, "missing"),
+
+            ProblemKind::CorruptFile =>
+                write!(f, "corrupt"),
+
+            ProblemKind::SizeMismatch =>
+                write!(f, "size-mismatch"),
+
+            ProblemKind::OrphanedFile =>
+                write!(f, "orphaned"),
+
+            ProblemKind::UnreadableSiteJson =>
+                write!(f, "unreadable-site-json"),
+        }
+    }
+}
+
+
+pub enum Error {
+    ListSites(io::Error),
+    LockSite(Domain, io::Error),
+    ListRevisions(Domain, ListRevisionsError),
+    ListBlobs(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ListSites(err) =>
+                write!(f, "Failed to list sites: {}", err),
+
+            Error::LockSite(domain, err) =>
+                write!(f, "Failed to lock {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadDir(err)) =>
+                write!(f, "Failed to read revisions dir for {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadRevisionJson(err)) =>
+                write!(f, "Failed to read revision json for {}: {}", domain, err),
+
+            Error::ListBlobs(err) =>
+                write!(f, "Failed to list blobs: {}", err),
+        }
+    }
+}
+
+
+enum BlobStatus {
+    Valid(usize),
+    Missing(),
+    Unreadable(String),
+    Corrupt(String),
+}
+
+
+// Checks that every file referenced by a site or one of its revisions exists, hashes to its name and
+// has the recorded size. Unreadable site.json files are quarantined, everything else is only changed
+// when repair is enabled. Each site is locked while it's checked
+pub fn check(storage: &Storage, site_locks: &SiteLocks, config: &Config, options: &Options) -> Result<Report, Error> {
+    let mut report = Report::default();
+    let mut blobs = HashMap::new();
+    let mut referenced = HashSet::new();
+    let mut all_sites_readable = true;
+
+    for site_root in site::list_site_roots(storage).map_err(Error::ListSites)? {
+        let _lock = site_locks.lock(&site_root)
+            .map_err(|err| Error::LockSite(site_root.domain.clone(), err))?;
+
+        let site = match site::get(&site_root) {
+            Ok(site) =>
+                Some(site),
+
+            Err(GetSiteError::SiteNotFound()) =>
+                None,
+
+            Err(err) => {
+                all_sites_readable = false;
+                report.problems.push(quarantine_site_json(&site_root, err));
+                None
+            },
+        };
+
+        let revisions = revision::list(&site_root)
+            .map_err(|err| Error::ListRevisions(site_root.domain.clone(), err))?;
+
+        let revision_hashes = revisions
+            .iter()
+            .flat_map(|revision| revision.routes.values())
+            .filter_map(|route_info| route_info.file_info())
+            .map(|file_info| file_info.hash.clone());
+
+        referenced.extend(revision_hashes);
+
+        if let Some(mut site) = site {
+            referenced.extend(site.routes.values().filter_map(|route_info| route_info.file_info()).map(|file_info| file_info.hash.clone()));
+            report.site_count += 1;
+
+            check_site(&site_root, &mut site, config, options, &mut blobs, &mut report.problems);
+        }
+
+        check_revisions(&site_root, &revisions, &mut blobs, &mut report.problems);
+    }
+
+    let blob_store = BlobStore::new(storage);
+
+    report.blob_count = blobs.len();
+    report.problems.extend(corrupt_problems(&blob_store, &blobs));
+
+    // Files referenced by an unreadable site.json are unknown, so nothing is removed in that case
+    let remove_orphans = options.repair && all_sites_readable;
+
+    check_orphans(&blob_store, &referenced, options.grace_period, remove_orphans, &mut report.problems)?;
+
+    Ok(report)
+}
+
+
+fn check_site(site_root: &SiteRoot, site: &mut Site, config: &Config, options: &Options, blobs: &mut HashMap<String, BlobStatus>, problems: &mut Vec<Problem>) {
+    let key = site_root.site_json_key();
+    let mut size_problems = vec![];
+
+    for (path, route_info) in site.routes.iter_mut() {
+        let file_info = match route_info {
+            RouteInfo::File{file_info, ..} =>
+                file_info,
+
+            RouteInfo::Redirect{..} =>
+                continue,
+        };
+
+        match check_blob(site_root, &file_info.hash, blobs) {
+            BlobStatus::Valid(size) if *size != file_info.size => {
+                size_problems.push(Problem{
+                    kind: ProblemKind::SizeMismatch,
+                    key: key.clone(),
+                    details: format!("Route {} has size {}, the file is {} bytes", path, file_info.size, size),
+                    repaired: options.repair,
+                });
+
+                file_info.size = *size;
+            },
+
+            status =>
+                problems.extend(blob_problem(&key, &path.to_string(), &file_info.hash, status)),
+        }
+    }
+
+    // The revision is not changed since the content of the site is the same
+    if options.repair && !size_problems.is_empty() {
+        match storage::write_json(site_root.storage(), &key, site) {
+            Ok(()) =>
+                index::update_site(&config.index, site),
+
+            Err(err) => {
+                for problem in size_problems.iter_mut() {
+                    problem.repaired = false;
+                    problem.details = format!("{}, failed to write site json: {}", problem.details, err);
+                }
+            },
+        }
+    }
+
+    problems.extend(size_problems);
+}
+
+// Revisions are only reported, they are snapshots and are never rewritten
+fn check_revisions(site_root: &SiteRoot, revisions: &[Revision], blobs: &mut HashMap<String, BlobStatus>, problems: &mut Vec<Problem>) {
+    for revision in revisions {
+        let key = site_root.revision_key(revision.revision);
+
+        let file_routes = revision.routes
+            .iter()
+            .filter_map(|(path, route_info)| route_info.file_info().map(|file_info| (path, file_info)));
+
+        for (path, file_info) in file_routes {
+
+            match check_blob(site_root, &file_info.hash, blobs) {
+                BlobStatus::Valid(size) if *size != file_info.size => {
+                    problems.push(Problem{
+                        kind: ProblemKind::SizeMismatch,
+                        key: key.clone(),
+                        details: format!("Route {} has size {}, the file is {} bytes", path, file_info.size, size),
+                        repaired: false,
+                    });
+                },
+
+                status =>
+                    problems.extend(blob_problem(&key, &path.to_string(), &file_info.hash, status)),
+            }
+        }
+    }
+}
+
+// Corrupt files are reported once per blob, missing files once per reference
+fn blob_problem(key: &str, path: &str, hash: &str, status: &BlobStatus) -> Option<Problem> {
+    match status {
+        BlobStatus::Valid(_) =>
+            None,
+
+        BlobStatus::Missing() =>
+            Some(Problem{
+                kind: ProblemKind::MissingFile,
+                key: key.to_string(),
+                details: format!("Route {} references the missing file {}", path, hash),
+                repaired: false,
+            }),
+
+        BlobStatus::Unreadable(err) =>
+            Some(Problem{
+                kind: ProblemKind::MissingFile,
+                key: key.to_string(),
+                details: format!("Route {} references the unreadable file {}: {}", path, hash, err),
+                repaired: false,
+            }),
+
+        BlobStatus::Corrupt(_) =>
+            None,
+    }
+}
+
+// The result is cached since blobs are shared between sites and revisions. Missing blobs are
+// checked again for each site since a site may still have the file in its legacy data dir
+fn check_blob<'a>(site_root: &SiteRoot, hash: &str, blobs: &'a mut HashMap<String, BlobStatus>) -> &'a BlobStatus {
+    let is_cached = match blobs.get(hash) {
+        Some(BlobStatus::Missing()) | None =>
+            false,
+
+        Some(_) =>
+            true,
+    };
+
+    if !is_cached {
+        let status = read_blob_status(site_root, hash);
+        blobs.insert(hash.to_string(), status);
+    }
+
+    &blobs[hash]
+}
+
+fn read_blob_status(site_root: &SiteRoot, hash: &str) -> BlobStatus {
+    match site_root.read_data_file(hash) {
+        Ok(data) => {
+            let actual_hash = util::sha256(&data);
+
+            if actual_hash == hash {
+                BlobStatus::Valid(data.len())
+            } else {
+                BlobStatus::Corrupt(actual_hash)
+            }
+        },
+
+        Err(err) if storage::is_not_found(&err) =>
+            BlobStatus::Missing(),
+
+        Err(err) =>
+            BlobStatus::Unreadable(err.to_string()),
+    }
+}
+
+fn corrupt_problems(blob_store: &BlobStore, blobs: &HashMap<String, BlobStatus>) -> Vec<Problem> {
+    blobs
+        .iter()
+        .filter_map(|(hash, status)| match status {
+            BlobStatus::Corrupt(actual_hash) =>
+                Some(Problem{
+                    kind: ProblemKind::CorruptFile,
+                    key: blob_store.blob_key(hash),
+                    details: format!("The content hashes to {}", actual_hash),
+                    repaired: false,
+                }),
+
+            _ =>
+                None,
+        })
+        .collect()
+}
+
+// Orphaned blobs are only removed after the grace period, the same rule as the garbage collector
+fn check_orphans(blob_store: &BlobStore, referenced: &HashSet<String>, grace_period: Duration, remove: bool, problems: &mut Vec<Problem>) -> Result<(), Error> {
+    let now = SystemTime::now();
+
+    for object in blob_store.list().map_err(Error::ListBlobs)? {
+        let hash = blob_store::hash_from_key(&object.key);
+
+        if referenced.contains(hash) {
+            continue;
+        }
+
+        let age = now.duration_since(object.modified)
+            .unwrap_or_default();
+
+        let (repaired, details) = if !remove || age < grace_period {
+            (false, format!("Not referenced by any site, last modified {} seconds ago", age.as_secs()))
+        } else {
+            match blob_store.remove_object(&object.key) {
+                Ok(()) =>
+                    (true, "Not referenced by any site, removed".to_string()),
+
+                Err(err) =>
+                    (false, format!("Not referenced by any site, failed to remove: {}", err)),
+            }
+        };
+
+        problems.push(Problem{
+            kind: ProblemKind::OrphanedFile,
+            key: object.key.clone(),
+            details,
+            repaired,
+        });
+    }
+
+    Ok(())
+}
+
+// Unparsable documents are moved out of the way so the site can be restored by hand.
+// Read errors may be temporary so the file is left as is in that case
+fn quarantine_site_json(site_root: &SiteRoot, err: GetSiteError) -> Problem {
+    let key = site_root.site_json_key();
+
+    let (details, is_parse_error) = match err {
+        GetSiteError::SiteNotFound() =>
+            ("Site not found".to_string(), false),
+
+        GetSiteError::ReadSiteJson(file::ReadJsonError::Open(err)) =>
+            (format!("Failed to read site json: {}", err), false),
+
+        GetSiteError::ReadSiteJson(err) =>
+            (format!("Failed to read site json: {}", err), true),
+
+        GetSiteError::MigrateSiteJson(err) =>
+            (format!("Failed to migrate site json: {}", err), true),
+    };
+
+    if !is_parse_error {
+        return Problem{
+            kind: ProblemKind::UnreadableSiteJson,
+            key,
+            details,
+            repaired: false,
+        };
+    }
+
+    let quarantine_key = site_root.quarantine_key(util::unix_timestamp(SystemTime::now()));
+
+    let result = site_root.storage().read(&key)
+        .and_then(|data| site_root.storage().write(&quarantine_key, &data))
+        .and_then(|()| site_root.storage().remove(&key));
+
+    let details = match result {
+        Ok(()) =>
+            format!("{}, moved to {}", details, quarantine_key),
+
+        Err(err) =>
+            format!("{}, failed to quarantine: {}", details, err),
+    };
+
+    Problem{
+        kind: ProblemKind::UnreadableSiteJson,
+        key,
+        details,
+        repaired: false,
+    }
+}
@@ -0,0 +1,118 @@
+use std::io;
+use std::fmt;
+use std::collections::{HashSet, BTreeMap};
+use std::time::Duration;
+use crate::orri::domain::Domain;
+use crate::orri::blob_store::{BlobStore, RemoveStats};
+use crate::orri::storage::Storage;
+use crate::orri::site::{self, GetSiteError};
+use crate::orri::site::revision::{self, ListRevisionsError};
+
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub interval: u64,
+    pub grace_period: u64,
+}
+
+
+pub enum Error {
+    ReadSitesDir(io::Error),
+    GetSite(Domain, GetSiteError),
+    ListRevisions(Domain, ListRevisionsError),
+    SkippedSites(usize),
+    RemoveBlobs(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ReadSitesDir(err) =>
+                write!(f, "Failed to read sites dir: {}", err),
+
+            Error::GetSite(domain, GetSiteError::SiteNotFound()) =>
+                write!(f, "Site not found: {}", domain),
+
+            Error::GetSite(domain, GetSiteError::ReadSiteJson(err)) =>
+                write!(f, "Failed to read site json for {}: {}", domain, err),
+
+            Error::GetSite(domain, GetSiteError::MigrateSiteJson(err)) =>
+                write!(f, "Failed to migrate site json for {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadDir(err)) =>
+                write!(f, "Failed to read revisions dir for {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadRevisionJson(err)) =>
+                write!(f, "Failed to read revision json for {}: {}", domain, err),
+
+            Error::SkippedSites(count) =>
+                write!(f, "Blobs were not removed, {} sites could not be read", count),
+
+            Error::RemoveBlobs(err) =>
+                write!(f, "Failed to remove blobs: {}", err),
+        }
+    }
+}
+
+
+// Mark and sweep, every blob referenced by a site or one of its revisions is kept.
+// A site that can't be read is logged and skipped, nothing is swept then so a broken site never loses its blobs
+pub fn collect_garbage(storage: &Storage, config: &Config) -> Result<RemoveStats, Error> {
+    let (referenced, skipped) = referenced_hashes(storage)?;
+
+    if skipped > 0 {
+        return Err(Error::SkippedSites(skipped));
+    }
+
+    let grace_period = Duration::from_secs(config.grace_period);
+
+    BlobStore::new(storage)
+        .remove_unreferenced(&referenced, grace_period)
+        .map_err(Error::RemoveBlobs)
+}
+
+// Returns the referenced hashes and the number of sites that were skipped
+fn referenced_hashes(storage: &Storage) -> Result<(HashSet<String>, usize), Error> {
+    let mut hashes = HashSet::new();
+    let mut skipped = 0;
+
+    for site_root in site::list_site_roots(storage).map_err(Error::ReadSitesDir)? {
+        let site_routes = match site::get(&site_root) {
+            Ok(site) =>
+                site.routes,
+
+            // The dir may only contain a lock file, the site was removed while collecting
+            // or site.json was quarantined by fsck. Revisions are still marked in the last case
+            Err(GetSiteError::SiteNotFound()) =>
+                BTreeMap::new(),
+
+            Err(err) => {
+                log::error!("Blob gc skipped site: {}", Error::GetSite(site_root.domain.clone(), err));
+                skipped += 1;
+                continue;
+            },
+        };
+
+        let revisions = match revision::list(&site_root) {
+            Ok(revisions) =>
+                revisions,
+
+            Err(err) => {
+                log::error!("Blob gc skipped site: {}", Error::ListRevisions(site_root.domain.clone(), err));
+                skipped += 1;
+                continue;
+            },
+        };
+
+        let site_hashes = revisions
+            .iter()
+            .flat_map(|revision| revision.routes.values())
+            .chain(site_routes.values())
+            .filter_map(|route_info| route_info.file_info())
+            .map(|file_info| file_info.hash.clone());
+
+        hashes.extend(site_hashes);
+    }
+
+    Ok((hashes, skipped))
+}
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use actix_http::http::header::{HeaderName, HeaderValue};
+use std::fmt;
+use std::str::FromStr;
+use crate::orri::util;
+
+
+pub const MAX_HEADERS: usize = 20;
+
+
+// Headers a site can set on its responses, everything else is controlled by the server
+const ALLOWED_HEADERS: &[&str] = &[
+    "access-control-allow-credentials",
+    "access-control-allow-headers",
+    "access-control-allow-methods",
+    "access-control-allow-origin",
+    "access-control-expose-headers",
+    "access-control-max-age",
+    "content-disposition",
+    "content-language",
+    "content-security-policy",
+    "content-security-policy-report-only",
+    "cross-origin-embedder-policy",
+    "cross-origin-opener-policy",
+    "cross-origin-resource-policy",
+    "link",
+    "permissions-policy",
+    "referrer-policy",
+    "strict-transport-security",
+    "x-content-type-options",
+    "x-frame-options",
+    "x-robots-tag",
+    "x-xss-protection",
+];
+
+// Rejected with their own error since they are the most likely to be tried.
+// Cookies are not allowed since all sites share the same parent domain
+const FORBIDDEN_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "set-cookie",
+];
+
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+}
+
+
+pub enum Error {
+    InvalidFormat(),
+    InvalidName(String),
+    InvalidValue(String),
+    Forbidden(String),
+    NotAllowed(String),
+    TooMany(),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidFormat() =>
+                write!(f, "Expected a header like «Name: value»"),
+
+            Error::InvalidName(name) =>
+                write!(f, "Invalid header name «{}»", name),
+
+            Error::InvalidValue(name) =>
+                write!(f, "Invalid value for header «{}»", name),
+
+            Error::Forbidden(name) =>
+                write!(f, "The header «{}» cannot be set by a site", name),
+
+            Error::NotAllowed(name) =>
+                write!(f, "The header «{}» is not in the list of allowed headers", name),
+
+            Error::TooMany() =>
+                write!(f, "More than {} headers", MAX_HEADERS),
+        }
+    }
+}
+
+
+impl Header {
+    pub fn new(name: &str, value: &str) -> Result<Header, Error> {
+        HeaderName::from_str(name)
+            .map_err(|_| Error::InvalidName(name.to_string()))?;
+
+        HeaderValue::from_str(value)
+            .map_err(|_| Error::InvalidValue(name.to_string()))?;
+
+        let lowercase_name = name.to_ascii_lowercase();
+
+        if FORBIDDEN_HEADERS.contains(&lowercase_name.as_str()) {
+            return Err(Error::Forbidden(name.to_string()));
+        }
+
+        if !ALLOWED_HEADERS.contains(&lowercase_name.as_str()) {
+            return Err(Error::NotAllowed(name.to_string()));
+        }
+
+        Ok(Header{
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+// A single "Name: value" line
+impl FromStr for Header {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Header, Error> {
+        let mut parts = s.splitn(2, ':');
+        let name = parts.next().unwrap_or_default().trim();
+
+        let value = parts.next()
+            .map(|value| value.trim())
+            .ok_or(Error::InvalidFormat())?;
+
+        Header::new(name, value)
+    }
+}
+
+
+pub struct LineError {
+    pub line: usize,
+    pub error: Error,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.error)
+    }
+}
+
+// One "Name: value" per line as entered on the manage page, empty lines are skipped
+pub fn parse_lines(text: &str) -> Result<Vec<Header>, LineError> {
+    let headers = text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            Header::from_str(line)
+                .map(|header| (index + 1, header))
+                .map_err(|error| LineError{ line: index + 1, error })
+        })
+        .collect::<Result<Vec<(usize, Header)>, LineError>>()?;
+
+    if let Some((line, _)) = headers.get(MAX_HEADERS) {
+        return Err(LineError{ line: *line, error: Error::TooMany() });
+    }
+
+    Ok(headers.into_iter().map(|(_, header)| header).collect())
+}
+
+pub fn format_lines(headers: &[Header]) -> String {
+    headers
+        .iter()
+        .map(|header| header.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Headers sent as json are checked the same way as headers parsed from text
+pub fn validate(headers: &[Header]) -> Result<Vec<Header>, Error> {
+    util::ensure(headers.len() <= MAX_HEADERS, Error::TooMany())?;
+
+    headers
+        .iter()
+        .map(|header| Header::new(&header.name, &header.value))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_error(s: &str) -> Option<String> {
+        Header::from_str(s)
+            .err()
+            .map(|err| err.to_string())
+    }
+
+    #[test]
+    fn parses_allowed_headers() {
+        let header = Header::from_str("  Content-Security-Policy: default-src 'self'; img-src *  ").ok();
+
+        assert_eq!(header.map(|header| header.to_string()), Some("Content-Security-Policy: default-src 'self'; img-src *".to_string()));
+        assert!(Header::from_str("x-frame-options:DENY").is_ok());
+    }
+
+    #[test]
+    fn rejects_headers_outside_of_the_allow_list() {
+        assert!(matches!(Header::from_str("Set-Cookie: session=1"), Err(Error::Forbidden(_))));
+        assert!(matches!(Header::from_str("Transfer-Encoding: chunked"), Err(Error::Forbidden(_))));
+        assert!(matches!(Header::from_str("Cache-Control: no-store"), Err(Error::NotAllowed(_))));
+        assert!(matches!(Header::from_str("X-Custom: value"), Err(Error::NotAllowed(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        assert!(matches!(Header::from_str("X-Frame-Options DENY"), Err(Error::InvalidFormat())));
+        assert!(matches!(Header::from_str(": DENY"), Err(Error::InvalidName(_))));
+        assert!(matches!(Header::from_str("X Frame: DENY"), Err(Error::InvalidName(_))));
+        assert!(matches!(Header::new("X-Frame-Options", "DENY\r\nSet-Cookie: a=b"), Err(Error::InvalidValue(_))));
+        assert_eq!(header_error("Link"), Some("Expected a header like «Name: value»".to_string()));
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_header() {
+        let result = parse_lines("X-Frame-Options: DENY\n\nCache-Control: no-store\n");
+
+        assert_eq!(result.err().map(|err| err.to_string()), Some("Line 3: The header «Cache-Control» is not in the list of allowed headers".to_string()));
+    }
+
+    #[test]
+    fn limits_the_number_of_headers() {
+        let text = "X-Robots-Tag: noindex\n".repeat(MAX_HEADERS);
+        let too_many = "X-Robots-Tag: noindex\n".repeat(MAX_HEADERS + 1);
+
+        assert_eq!(parse_lines(&text).ok().map(|headers| headers.len()), Some(MAX_HEADERS));
+        assert!(matches!(parse_lines(&too_many), Err(LineError{ line, error: Error::TooMany() }) if line == MAX_HEADERS + 1));
+        assert!(validate(&parse_lines(&text).ok().unwrap_or_default()).is_ok());
+    }
+
+    #[test]
+    fn formats_lines_that_parse_back() {
+        let headers = parse_lines("Referrer-Policy: no-referrer\nX-Content-Type-Options: nosniff").ok().unwrap_or_default();
+
+        let parsed = parse_lines(&format_lines(&headers)).ok().unwrap_or_default();
+
+        assert_eq!(headers.len(), 2);
+        assert!(parsed == headers);
+    }
+}
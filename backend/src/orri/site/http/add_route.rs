@@ -8,6 +8,7 @@ use crate::orri::slowhtml::html;
 use crate::orri::slowhtml::attributes as attrs;
 use crate::orri::page::{self, Page, Head};
 use crate::orri::route::Route;
+use crate::orri::redirect::Status;
 use crate::orri::session_data::{SessionData};
 use crate::orri::http as http_helper;
 use http::header;
@@ -17,33 +18,40 @@ use std::str::FromStr;
 enum Error {
     ParseDomain(domain::Error),
     GetSite(GetSiteError),
+    Canceled(),
 }
 
 pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>) -> HttpResponse {
-    handle(&state, &domain)
-        .map(|site| prepare_response(site, &session))
+    let session_data = SessionData::from_session(&session);
+
+    let result = http_helper::block(move || handle(&state, session_data, &domain), Error::Canceled()).await;
+
+    result
+        .map(|(site, client_has_key)| prepare_response(site, client_has_key))
         .unwrap_or_else(handle_error)
 }
 
 
-fn handle(state: &AppState, domain_str: &str) -> Result<Site, Error> {
+// Returns the site and if the session can change it without entering the key
+fn handle(state: &AppState, session_data: Option<SessionData>, domain_str: &str) -> Result<(Site, bool), Error> {
 
     let domain = Domain::from_str(domain_str)
         .map_err(Error::ParseDomain)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
 
-    site::get(&site_root)
-        .map_err(Error::GetSite)
-}
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
 
+    let client_has_key = session_data
+        .and_then(|session_data| session_data.get_site_credential(&state.sessions, &site.domain))
+        .is_some();
+
+    Ok((site, client_has_key))
+}
 
-fn prepare_response(site: Site, session: &Session) -> HttpResponse {
 
-    let client_has_key = SessionData::from_session(session)
-        .and_then(|session_data| session_data.get_site_key(&site.domain))
-        .and_then(|key_from_session| site.key.verify(&key_from_session).ok())
-        .unwrap_or(false);
+fn prepare_response(site: Site, client_has_key: bool) -> HttpResponse {
 
     let html = build_page(&site, client_has_key).render();
 
@@ -61,6 +69,11 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::GetSite(err) => {
             handle_get_site_error(err)
         },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -75,6 +88,11 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -90,7 +108,13 @@ fn build_page(site: &Site, client_has_key: bool) -> Page {
 
 
 fn build_body(site: &Site, client_has_key: bool) -> Vec<Html> {
-    let add_route = Route::AddRouteJson();
+    let add_route = Route::UploadRouteJson(site.domain.to_string());
+    let add_redirect = Route::AddRedirectJson(site.domain.to_string());
+
+    let status_options = Status::all()
+        .into_iter()
+        .map(|status| status_option(status, status == Status::default()))
+        .collect::<Vec<Html>>();
 
     vec![
         page::navbar(
@@ -109,7 +133,10 @@ fn build_body(site: &Site, client_has_key: bool) -> Vec<Html> {
                         &[
                             attrs::id("form"),
                             attrs::attribute_trusted_name("data-api-method", &add_route.request_method().to_string()),
-                            attrs::attribute_trusted_name("data-api-url", &add_route.to_string())
+                            attrs::attribute_trusted_name("data-api-url", &add_route.to_string()),
+                            attrs::attribute_trusted_name("data-api-encoding", "multipart"),
+                            attrs::attribute_trusted_name("data-redirect-api-method", &add_redirect.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-redirect-api-url", &add_redirect.to_string()),
                         ], &[
                         html::div(&[attrs::class("form-group")], &[
                             html::label(&[attrs::class("form-label")], &[
@@ -154,6 +181,15 @@ fn build_body(site: &Site, client_has_key: bool) -> Vec<Html> {
                             ]),
                         ),
                         html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Type")]),
+                                html::select(&[attrs::class("form-select"), attrs::name("kind"), attrs::id("kind")], &[
+                                    html::option(&[attrs::value("file"), attrs::selected()], &[html::text("File")]),
+                                    html::option(&[attrs::value("redirect")], &[html::text("Redirect")]),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group"), attrs::id("file-group")], &[
                             html::label(&[attrs::class("form-label")], &[
                                 html::div(&[], &[html::text("File")]),
                                 html::input(&[
@@ -167,6 +203,29 @@ fn build_body(site: &Site, client_has_key: bool) -> Vec<Html> {
                                 ]),
                             ]),
                         ]),
+                        html::div(&[attrs::class("display-none"), attrs::id("redirect-group")], &[
+                            html::div(&[attrs::class("form-group")], &[
+                                html::label(&[attrs::class("form-label")], &[
+                                    html::div(&[], &[html::text("Redirect to")]),
+                                    html::input(&[
+                                        attrs::type_("text"),
+                                        attrs::class("form-input"),
+                                        attrs::name("target"),
+                                        attrs::id("target"),
+                                        attrs::placeholder("i.e. /new-page or https://example.com/"),
+                                    ]),
+                                    html::p(&[attrs::class("form-input-hint")], &[
+                                        html::text("A path on this site or an absolute url"),
+                                    ]),
+                                ]),
+                            ]),
+                            html::div(&[attrs::class("form-group")], &[
+                                html::label(&[attrs::class("form-label")], &[
+                                    html::div(&[], &[html::text("Status")]),
+                                    html::select(&[attrs::class("form-select"), attrs::name("status")], &status_options),
+                                ]),
+                            ]),
+                        ]),
                         html::div(&[attrs::class("form-group margin-top-20")], &[
                             html::button(
                                 &[
@@ -184,3 +243,14 @@ fn build_body(site: &Site, client_has_key: bool) -> Vec<Html> {
         html::script(&[attrs::src("/static/route_form.js")], &[]),
     ]
 }
+
+fn status_option(status: Status, selected: bool) -> Html {
+    let text = format!("{} {}", status, status.status_code().canonical_reason().unwrap_or_default());
+    let mut attributes = vec![attrs::value(&status.to_string())];
+
+    if selected {
+        attributes.push(attrs::selected());
+    }
+
+    html::option(&attributes, &[html::text(&text)])
+}
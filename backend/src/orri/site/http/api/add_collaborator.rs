@@ -0,0 +1,274 @@
+use actix_web::{web, HttpResponse};
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::collaborator::{self, Collaborator, Role};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::http as http_helper;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::io;
+
+
+#[derive(Deserialize)]
+pub struct Request {
+    key: Option<String>,
+    name: String,
+
+    // owner, editor or viewer
+    role: String,
+}
+
+// The key of the collaborator is only shown once, the site only keeps its hash
+#[derive(Serialize)]
+pub struct Response {
+    id: String,
+    name: String,
+    role: Role,
+    key: String,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    InvalidCollaborator(collaborator::Error),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    PersistSite(site::PersistSiteError),
+}
+
+pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let has_key = request_data.key.is_some();
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(&state, &blocking_session_data, &domain, &request_data), Error::Canceled()).await;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if has_key && result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(state: &AppState, session_data: &SessionData, domain_str: &str, request_data: &Request) -> Result<(Collaborator, String), Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let role = Role::from_str(&request_data.role)
+        .map_err(Error::InvalidCollaborator)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    let provided_key = get_provided_key(request_data, session_data, &state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(&site, &Access::ManageSite())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+    let (collaborator, key) = Collaborator::new(&state.config.site_key, &request_data.name, role, SystemTime::now())
+        .map_err(Error::InvalidCollaborator)?;
+
+    site.add_collaborator(collaborator.clone())
+        .map_err(Error::InvalidCollaborator)?;
+
+    site.persist(&state.config.site, &site_root)
+        .map_err(Error::PersistSite)?;
+
+    // The key was verified, the session can manage the site without it until the capability expires
+    if let Some(key) = &request_data.key {
+        let _ = session_data.add_site(&state.sessions, &site, &state.config.site, key);
+    }
+
+    Ok((collaborator, key))
+}
+
+fn get_provided_key(request_data: &Request, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    request_data.key.clone()
+        .map(Credential::Key)
+        .or_else(|| session_data.get_site_credential(sessions, domain))
+}
+
+
+fn prepare_response((collaborator, key): (Collaborator, String)) -> HttpResponse {
+    http_helper::no_cache_headers(&mut HttpResponse::Created())
+        .json(Response{
+            id: collaborator.id,
+            name: collaborator.name,
+            role: collaborator.role,
+            key,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::InvalidCollaborator(err) =>
+            handle_collaborator_error(err),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_collaborator_error(err: collaborator::Error) -> HttpResponse {
+    match err {
+        collaborator::Error::SiteKey(err) => {
+            log::error!("Failed to create collaborator key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to create a key for the collaborator"))
+        },
+
+        err =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&err.to_string())),
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
@@ -0,0 +1,184 @@
+use actix_web::{web, HttpResponse};
+use actix_session::Session;
+use serde::{Deserialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::session_data::{self, SessionData, Credential};
+use crate::orri::http as http_helper;
+use std::str::FromStr;
+
+
+#[derive(Deserialize)]
+pub struct Request {
+    key: String,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    VerifyKey(site_key::VerifyError),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    SessionData(session_data::Error),
+    Canceled(),
+}
+
+pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(&state, &blocking_session_data, &domain, &request_data), Error::Canceled()).await;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+// Logs in to the site without changing it, this is how viewers get access to the revisions,
+// collaborators and tokens on the manage page. Any key of the site is accepted
+fn handle(state: &AppState, session_data: &SessionData, domain_str: &str, request_data: &Request) -> Result<Site, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    let has_valid_key = Credential::Key(request_data.key.clone()).verify(&site, &Access::Read())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+    session_data.add_site(&state.sessions, &site, &state.config.site, &request_data.key)
+        .map_err(Error::SessionData)?;
+
+    Ok(site)
+}
+
+
+fn prepare_response(_site: Site) -> HttpResponse {
+    http_helper::no_cache_headers(&mut HttpResponse::NoContent())
+        .finish()
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::SessionData(err) =>
+            handle_session_data_error(err),
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to log in"))
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_session_data_error(err: session_data::Error) -> HttpResponse {
+    match err {
+        session_data::Error::QuotaMaxSites() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total sites reached"))
+        },
+
+        session_data::Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
+        },
+
+        session_data::Error::ReadSession(err) => {
+            log::error!("Failed to read session: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
+        },
+
+        session_data::Error::WriteSession(err) => {
+            log::error!("Failed to write session: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
+        },
+    }
+}
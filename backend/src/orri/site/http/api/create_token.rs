@@ -0,0 +1,312 @@
+use actix_web::{web, HttpResponse};
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::domain::{self, Domain};
+use crate::orri::url_path::{self, UrlPath};
+use crate::orri::site_key;
+use crate::orri::api_token::{self, ApiToken, Access, Scope};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::http as http_helper;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::io;
+
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    key: Option<String>,
+    name: String,
+
+    // read, write_routes or delete_site
+    scope: String,
+
+    // Only routes starting with the prefix can be changed with the token
+    path_prefix: Option<String>,
+    expires_in_days: Option<u64>,
+}
+
+// The token is only shown once, the site only keeps its hash
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    id: String,
+    token: String,
+    name: String,
+    scope: Scope,
+    path_prefix: Option<UrlPath>,
+    expires: Option<u64>,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    ParsePath(url_path::Error),
+    InvalidToken(api_token::Error),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    PersistSite(site::PersistSiteError),
+}
+
+pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let has_key = request_data.key.is_some();
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(&state, &blocking_session_data, &domain, &request_data), Error::Canceled()).await;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if has_key && result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(state: &AppState, session_data: &SessionData, domain_str: &str, request_data: &Request) -> Result<(ApiToken, String), Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let scope = Scope::from_str(&request_data.scope)
+        .map_err(Error::InvalidToken)?;
+
+    let path_prefix = parse_optional_path(&request_data.path_prefix)?;
+
+    let time = SystemTime::now();
+
+    let (token, secret) = ApiToken::new(&request_data.name, scope, path_prefix, request_data.expires_in_days, time)
+        .map_err(Error::InvalidToken)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    let provided_key = get_provided_key(request_data, session_data, &state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(&site, &Access::ManageSite())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+    site.add_token(token.clone(), time)
+        .map_err(Error::InvalidToken)?;
+
+    site.persist(&state.config.site, &site_root)
+        .map_err(Error::PersistSite)?;
+
+    // The key was verified, the session can manage the site without it until the capability expires
+    if let Some(key) = &request_data.key {
+        let _ = session_data.add_site(&state.sessions, &site, &state.config.site, key);
+    }
+
+    Ok((token, secret))
+}
+
+fn parse_optional_path(value: &Option<String>) -> Result<Option<UrlPath>, Error> {
+    match value.as_ref().map(|path| path.trim()) {
+        Some("") | None =>
+            Ok(None),
+
+        Some(path) =>
+            UrlPath::from_str(path)
+                .map(Some)
+                .map_err(Error::ParsePath),
+    }
+}
+
+// Tokens can't be used to create other tokens
+fn get_provided_key(request_data: &Request, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    request_data.key.clone()
+        .map(Credential::Key)
+        .or_else(|| session_data.get_site_credential(sessions, domain))
+}
+
+
+fn prepare_response((token, secret): (ApiToken, String)) -> HttpResponse {
+    http_helper::no_cache_headers(&mut HttpResponse::Created())
+        .json(Response{
+            id: token.id,
+            token: secret,
+            name: token.name,
+            scope: token.scope,
+            path_prefix: token.path_prefix,
+            expires: token.expires,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::ParsePath(err) =>
+            handle_parse_path_error(err),
+
+        Error::InvalidToken(err) =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&err.to_string())),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_parse_path_error(err: url_path::Error) -> HttpResponse {
+    match err {
+        url_path::Error::MustStartWithSlash() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path must start with a slash")),
+
+        url_path::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path is too long")),
+
+        url_path::Error::ContainsDisallowedChars() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path contains disallowed characters")),
+
+        url_path::Error::ContainsDoubleDot() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path cannot contain double dots")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
@@ -0,0 +1,583 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::error::PayloadError;
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, FileInfo, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::file;
+use crate::orri::archive;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::url_path::{self, UrlPath};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::route::Route;
+use crate::orri::http as http_helper;
+use futures::StreamExt;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::io::{self, Read, Write};
+use std::fs;
+use tempfile;
+
+
+#[derive(Deserialize)]
+pub struct QueryParams {
+    revision: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    manage_url: String,
+    route_count: usize,
+    revision: u64,
+
+    // Redirect routes are not part of the archive, the ones that no file replaced are kept
+    kept_redirects: Vec<UrlPath>,
+}
+
+#[derive(Serialize)]
+pub struct EntriesErrorResponse {
+    error: String,
+    entries: Vec<EntryReport>,
+}
+
+#[derive(Serialize)]
+pub struct EntryReport {
+    name: String,
+    error: String,
+}
+
+// The request is not Send, the credentials are read from it before the work moves to the thread pool
+#[derive(Clone)]
+struct ProvidedCredentials {
+    site_key: Option<String>,
+    bearer_token: Option<String>,
+    session_data: SessionData,
+}
+
+struct DeployFile {
+    path: UrlPath,
+    file_info: FileInfo,
+    hashed_file: file::HashedFile,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    StaleRevision(),
+    PrepareDirectories(io::Error),
+    CreateTempFile(io::Error),
+    ReadPayload(PayloadError),
+    WriteTempFile(io::Error),
+    ArchiveTooLarge(),
+    ReadArchive(archive::Error),
+    InvalidEntries(Vec<EntryReport>),
+    Deploy(site::DeployError),
+    WriteFile(io::Error),
+    PersistSite(site::PersistSiteError),
+}
+
+enum EntryError {
+    ParsePath(url_path::Error),
+    DuplicateRoute(),
+    QuotaMaxSize(),
+    QuotaMaxRoutes(),
+    CreateTempFile(io::Error),
+    Read(io::Error),
+    Write(io::Error),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>, query: web::Query<QueryParams>, payload: web::Payload) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let credentials = ProvidedCredentials{
+        site_key: site_key_header(&req),
+        bearer_token: http_helper::bearer_token(&req),
+        session_data,
+    };
+
+    handle(credentials, state, &domain, query.into_inner(), payload).await
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+async fn handle(credentials: ProvidedCredentials, state: web::Data<AppState>, domain_str: &str, query: QueryParams, payload: web::Payload) -> Result<(Site, Vec<UrlPath>), Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let blocking_state = state.clone();
+    let blocking_domain = domain.clone();
+    let blocking_credentials = credentials.clone();
+
+    // Checked before the archive is read, the key is verified again under the site lock
+    let (site, archive_file) = http_helper::block(move || {
+        let site_root = site::SiteRoot::new(&blocking_state.storage, blocking_domain);
+
+        let site = site::get(&site_root)
+            .map_err(Error::GetSite)?;
+
+        verify_key(&blocking_credentials, &blocking_state, &site)?;
+
+        site_root.prepare_directories()
+            .map_err(Error::PrepareDirectories)?;
+
+        let archive_file = tempfile::tempfile()
+            .map_err(Error::CreateTempFile)?;
+
+        Ok((site, archive_file))
+    }, Error::Canceled()).await?;
+
+    let limits = site.quota.limits(&state.config.site);
+    let mut archive_file = write_payload(payload, archive_file, limits.max_size).await?;
+
+    http_helper::block(move || deploy(&credentials, &state, domain, &query, &mut archive_file, &limits), Error::Canceled()).await
+}
+
+fn deploy(credentials: &ProvidedCredentials, state: &AppState, domain: Domain, query: &QueryParams, archive_file: &mut fs::File, limits: &site::QuotaLimits) -> Result<(Site, Vec<UrlPath>), Error> {
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let files = extract_archive(&site_root, archive_file, limits)?;
+
+    // The site is read again while holding the lock since it may have changed during the upload
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    verify_key(credentials, state, &site)?;
+
+    util::ensure(site.is_expected_revision(query.revision), Error::StaleRevision())?;
+
+    let routes = files
+        .iter()
+        .map(|file| (file.path.clone(), file.file_info.clone()))
+        .collect();
+
+    let kept_redirects = site.deploy_uploaded_routes(&state.config.site, routes)
+        .map_err(Error::Deploy)?;
+
+    files
+        .into_iter()
+        .try_for_each(|file| site_root.blob_store().persist(file.hashed_file))
+        .map_err(Error::WriteFile)?;
+
+    site.persist(&state.config.site, &site_root)
+        .map_err(Error::PersistSite)?;
+
+    Ok((site, kept_redirects))
+}
+
+fn verify_key(credentials: &ProvidedCredentials, state: &AppState, site: &Site) -> Result<(), Error> {
+    let provided_key = credentials.get_provided_key(&state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(site, &Access::WriteSite())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())
+}
+
+// The file is moved to the thread pool for each chunk and handed back when the chunk is written
+async fn write_payload(mut payload: web::Payload, mut archive_file: fs::File, max_size: usize) -> Result<fs::File, Error> {
+    let mut size = 0;
+
+    while let Some(chunk) = payload.next().await {
+        let bytes = chunk.map_err(Error::ReadPayload)?;
+
+        size += bytes.len();
+        util::ensure(size < max_size, Error::ArchiveTooLarge())?;
+
+        archive_file = http_helper::block(move || {
+            archive_file.write_all(&bytes)
+                .map_err(Error::WriteTempFile)?;
+
+            Ok(archive_file)
+        }, Error::Canceled()).await?;
+    }
+
+    Ok(archive_file)
+}
+
+fn extract_archive(site_root: &site::SiteRoot, archive_file: &mut fs::File, limits: &site::QuotaLimits) -> Result<Vec<DeployFile>, Error> {
+    let time = SystemTime::now();
+    let mut files: Vec<DeployFile> = vec![];
+    let mut reports: Vec<EntryReport> = vec![];
+    let mut total_size = 0;
+    let mut quota_exceeded = false;
+
+    archive::for_each_file(archive_file, |name, reader| {
+        if quota_exceeded {
+            return;
+        }
+
+        let result = entry_path(name)
+            .map(|maybe_path| maybe_path.map(|path| {
+                extract_entry(site_root, &files, path, reader, limits, total_size, time)
+            }));
+
+        match result {
+            Ok(None) =>
+                (),
+
+            Ok(Some(Ok(file))) => {
+                total_size += file.file_info.size;
+                files.push(file);
+            },
+
+            Ok(Some(Err(err))) | Err(err) => {
+                quota_exceeded = is_quota_error(&err);
+
+                reports.push(EntryReport{
+                    name: name.to_string(),
+                    error: entry_error_message(err),
+                });
+            },
+        }
+    }).map_err(Error::ReadArchive)?;
+
+    util::ensure(reports.is_empty(), Error::InvalidEntries(reports))?;
+
+    Ok(files)
+}
+
+// Maps an archive entry name to a route, index.html in the archive root becomes the site root
+fn entry_path(name: &str) -> Result<Option<UrlPath>, EntryError> {
+    let relative_name = name
+        .trim_start_matches("./")
+        .trim_start_matches('/');
+
+    if relative_name.is_empty() || relative_name.starts_with("__MACOSX/") {
+        return Ok(None);
+    }
+
+    if relative_name == "index.html" {
+        return Ok(Some(UrlPath::root()));
+    }
+
+    UrlPath::from_str(&format!("/{}", relative_name))
+        .map(Some)
+        .map_err(EntryError::ParsePath)
+}
+
+fn extract_entry(site_root: &site::SiteRoot, files: &[DeployFile], path: UrlPath, reader: &mut dyn Read, limits: &site::QuotaLimits, total_size: usize, time: SystemTime) -> Result<DeployFile, EntryError> {
+    util::ensure(!files.iter().any(|file| file.path == path), EntryError::DuplicateRoute())?;
+    util::ensure(files.len() < limits.max_routes, EntryError::QuotaMaxRoutes())?;
+
+    let mut writer = file::HashWriter::new(&site_root.blob_store().temp_path())
+        .map_err(EntryError::CreateTempFile)?;
+
+    let mut buffer = [0; 8192];
+
+    loop {
+        let count = reader.read(&mut buffer)
+            .map_err(EntryError::Read)?;
+
+        if count == 0 {
+            break;
+        }
+
+        util::ensure(total_size + writer.size() + count < limits.max_size, EntryError::QuotaMaxSize())?;
+
+        writer.write(&buffer[..count])
+            .map_err(EntryError::Write)?;
+    }
+
+    let hashed_file = writer.finish();
+    let mime_type = mime_guess::from_path(path.relative_path())
+        .first_or_octet_stream()
+        .to_string();
+
+    Ok(DeployFile{
+        file_info: FileInfo::from_hashed_file(&hashed_file, mime_type, time),
+        path,
+        hashed_file,
+    })
+}
+
+fn is_quota_error(err: &EntryError) -> bool {
+    matches!(err, EntryError::QuotaMaxSize() | EntryError::QuotaMaxRoutes())
+}
+
+fn entry_error_message(err: EntryError) -> String {
+    match err {
+        EntryError::ParsePath(err) =>
+            parse_path_error_message(err).to_string(),
+
+        EntryError::DuplicateRoute() =>
+            "The route is defined more than once".to_string(),
+
+        EntryError::QuotaMaxSize() =>
+            "Max total size reached".to_string(),
+
+        EntryError::QuotaMaxRoutes() =>
+            "Max routes reached".to_string(),
+
+        EntryError::CreateTempFile(err) => {
+            log::error!("Failed to create temp file: {}", err);
+            "Failed to store file".to_string()
+        },
+
+        EntryError::Read(err) =>
+            format!("Failed to read file: {}", err),
+
+        EntryError::Write(err) => {
+            log::error!("Failed to write temp file: {}", err);
+            "Failed to store file".to_string()
+        },
+    }
+}
+
+fn parse_path_error_message(err: url_path::Error) -> &'static str {
+    match err {
+        url_path::Error::MustStartWithSlash() =>
+            "The path must start with a slash",
+
+        url_path::Error::TooLong() =>
+            "The path is too long",
+
+        url_path::Error::ContainsDisallowedChars() =>
+            "The path contains disallowed characters",
+
+        url_path::Error::ContainsDoubleDot() =>
+            "The path cannot contain double dots",
+    }
+}
+
+fn site_key_header(req: &HttpRequest) -> Option<String> {
+    req.headers().get(http_helper::SITE_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+impl ProvidedCredentials {
+    fn get_provided_key(&self, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+        self.site_key.clone()
+            .map(Credential::Key)
+            .or_else(|| self.bearer_token.clone().map(Credential::Token))
+            .or_else(|| self.session_data.get_site_credential(sessions, domain))
+    }
+}
+
+
+fn prepare_response((site, kept_redirects): (Site, Vec<UrlPath>)) -> HttpResponse {
+    let manage_route = Route::ManageSite(site.domain.to_string());
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            manage_url: manage_route.to_string(),
+            route_count: site.routes.len(),
+            revision: site.revision,
+            kept_redirects,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::StaleRevision() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("The site has been changed by someone else, reload and try again")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::PrepareDirectories(err) => {
+            log::error!("Failed to prepare site directories: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store archive"))
+        },
+
+        Error::CreateTempFile(err) => {
+            log::error!("Failed to create temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store archive"))
+        },
+
+        Error::ReadPayload(err) => {
+            log::error!("Failed to read payload: {}", err);
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Failed to read request body"))
+        },
+
+        Error::WriteTempFile(err) => {
+            log::error!("Failed to write temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store archive"))
+        },
+
+        Error::ArchiveTooLarge() =>
+            HttpResponse::PayloadTooLarge()
+                .json(http::Error::from_str("Max total size reached")),
+
+        Error::ReadArchive(err) =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&err.to_string())),
+
+        Error::InvalidEntries(entries) =>
+            HttpResponse::BadRequest()
+                .json(EntriesErrorResponse{
+                    error: "The archive contains invalid entries".to_string(),
+                    entries,
+                }),
+
+        Error::Deploy(err) =>
+            handle_deploy_error(err),
+
+        Error::WriteFile(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_deploy_error(err: site::DeployError) -> HttpResponse {
+    match err {
+        site::DeployError::QuotaMaxSize() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total size reached"))
+        },
+
+        site::DeployError::QuotaMaxRoutes() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max routes reached"))
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
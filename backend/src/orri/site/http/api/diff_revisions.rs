@@ -0,0 +1,206 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, GetSiteError};
+use crate::orri::site::revision::{self, Revision, RevisionDiff, GetRevisionError};
+use crate::orri::http;
+use crate::orri::domain::{self, Domain};
+use crate::orri::util;
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::url_path::UrlPath;
+use crate::orri::http as http_helper;
+use std::str::FromStr;
+
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct QueryParams {
+    from: u64,
+    to: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    from: u64,
+    to: u64,
+    added: Vec<UrlPath>,
+    removed: Vec<UrlPath>,
+    changed: Vec<UrlPath>,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    GetSite(GetSiteError),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    InvalidKey(),
+    GetRevision(u64, GetRevisionError),
+    Canceled(),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>, query: web::Query<QueryParams>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+    let query = query.into_inner();
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &session_data, &domain, &query), Error::Canceled()).await;
+
+    result
+        .map(|diff| prepare_response(&query, diff))
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, domain_str: &str, query: &QueryParams) -> Result<RevisionDiff, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    // Make sure the site exists and may be read before looking for revisions
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    verify_read_access(bearer_token, state, session_data, &site)?;
+
+    let from = get_revision(&site_root, query.from)?;
+    let to = get_revision(&site_root, query.to)?;
+
+    Ok(revision::diff(&from, &to))
+}
+
+// Revisions are only shown to collaborators, including viewers, and to tokens
+fn verify_read_access(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, site: &Site) -> Result<(), Error> {
+    let credential = bearer_token
+        .map(Credential::Token)
+        .or_else(|| session_data.get_site_credential(&state.sessions, &site.domain))
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_access = credential.verify(site, &Access::Read())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_access, Error::InvalidKey())
+}
+
+fn get_revision(site_root: &site::SiteRoot, revision: u64) -> Result<Revision, Error> {
+    revision::get(site_root, revision)
+        .map_err(|err| Error::GetRevision(revision, err))
+}
+
+
+fn prepare_response(query: &QueryParams, diff: RevisionDiff) -> HttpResponse {
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            from: query.from,
+            to: query.to,
+            added: diff.added,
+            removed: diff.removed,
+            changed: diff.changed,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::GetRevision(revision, err) =>
+            handle_get_revision_error(revision, err),
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to read site"))
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_get_revision_error(revision: u64, err: GetRevisionError) -> HttpResponse {
+    match err {
+        GetRevisionError::RevisionNotFound() => {
+            HttpResponse::NotFound()
+                .json(http::Error::from_str(&format!("Revision not found: {}", revision)))
+        },
+
+        GetRevisionError::ReadRevisionJson(err) => {
+            log::error!("Failed to read revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to read revision"))
+        },
+    }
+}
@@ -0,0 +1,344 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, RedirectInfo, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::url_path::{self, UrlPath};
+use crate::orri::redirect::{self, Target, Status};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::route::Route;
+use crate::orri::http as http_helper;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::convert::TryFrom;
+use std::io;
+
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    path: String,
+    target: String,
+    status: u16,
+    key: Option<String>,
+    revision: Option<u64>,
+}
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    manage_url: String,
+    revision: u64,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    ParsePath(url_path::Error),
+    ParseTarget(redirect::TargetError),
+    ParseStatus(redirect::StatusError),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    StaleRevision(),
+    UpdateRoute(site::UpdateRouteError),
+    PersistSite(site::PersistSiteError),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+    let has_key = request_data.key.is_some();
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &blocking_session_data, &domain, &request_data), Error::Canceled()).await;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if has_key && result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, domain_str: &str, request_data: &Request) -> Result<Site, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let path = UrlPath::from_str(&request_data.path)
+        .map_err(Error::ParsePath)?;
+
+    let target = Target::from_str(&request_data.target)
+        .map_err(Error::ParseTarget)?;
+
+    let status = Status::try_from(request_data.status)
+        .map_err(Error::ParseStatus)?;
+
+    let redirect = RedirectInfo::new(target, status, SystemTime::now());
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    let provided_key = get_provided_key(bearer_token, request_data, session_data, &state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(&site, &Access::WriteRoutes(vec![&path]))
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+    util::ensure(site.is_expected_revision(request_data.revision), Error::StaleRevision())?;
+
+    site.update_redirect_route(path, redirect)
+        .map_err(Error::UpdateRoute)?;
+
+    site.persist(&state.config.site, &site_root)
+        .map_err(Error::PersistSite)?;
+
+    // The key was verified, the session can manage the site without it until the capability expires
+    if let Some(key) = &request_data.key {
+        let _ = session_data.add_site(&state.sessions, &site, &state.config.site, key);
+    }
+
+    Ok(site)
+}
+
+fn get_provided_key(bearer_token: Option<String>, request_data: &Request, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    request_data.key.clone()
+        .map(Credential::Key)
+        .or_else(|| bearer_token.map(Credential::Token))
+        .or_else(|| session_data.get_site_credential(sessions, domain))
+}
+
+
+fn prepare_response(site: Site) -> HttpResponse {
+    let manage_route = Route::ManageSite(site.domain.to_string());
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            manage_url: manage_route.to_string(),
+            revision: site.revision,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::ParsePath(err) =>
+            handle_parse_path_error(err),
+
+        Error::ParseTarget(err) =>
+            handle_parse_target_error(err),
+
+        Error::ParseStatus(err) =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&err.to_string())),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::StaleRevision() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("The site has been changed by someone else, reload and try again")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::UpdateRoute(err) => {
+            handle_update_route_error(err)
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_parse_path_error(err: url_path::Error) -> HttpResponse {
+    match err {
+        url_path::Error::MustStartWithSlash() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path must start with a slash")),
+
+        url_path::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path is too long")),
+
+        url_path::Error::ContainsDisallowedChars() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path contains disallowed characters")),
+
+        url_path::Error::ContainsDoubleDot() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path cannot contain double dots")),
+    }
+}
+
+fn handle_parse_target_error(err: redirect::TargetError) -> HttpResponse {
+    match err {
+        redirect::TargetError::Empty() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The redirect target cannot be empty")),
+
+        redirect::TargetError::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The redirect target is too long")),
+
+        redirect::TargetError::ContainsDisallowedChars() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The redirect target cannot contain whitespace or control characters")),
+
+        redirect::TargetError::MustBePathOrUrl() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The redirect target must be a path starting with a slash or a http(s) url")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_update_route_error(err: site::UpdateRouteError) -> HttpResponse {
+    match err {
+        site::UpdateRouteError::RouteNotFound() => {
+            HttpResponse::NotFound()
+                .json(http::Error::from_str("Route not found"))
+        },
+
+        site::UpdateRouteError::QuotaMaxSize() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total size reached"))
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
@@ -1,19 +1,23 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use actix_session::Session;
 use serde::{Deserialize, Serialize};
 use crate::orri::app_state::AppState;
 use crate::orri::site::{self, Site, FileInfo, GetSiteError};
+use crate::orri::site::headers::{self, Header};
 use crate::orri::http;
 use crate::orri::util;
 use crate::orri::domain::{self, Domain};
 use crate::orri::site_key;
+use crate::orri::api_token::Access;
 use crate::orri::url_path::{self, UrlPath};
-use crate::orri::session_data::{SessionData};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
 use crate::orri::route::Route;
 use crate::orri::http as http_helper;
 use data_url::{DataUrl, DataUrlError, forgiving_base64};
 use std::time::SystemTime;
 use std::str::FromStr;
+use std::io;
 
 
 #[derive(Deserialize)]
@@ -22,7 +26,11 @@ pub struct Request {
     domain: String,
     path: String,
     data_url: String,
+
+    // Replaces the custom response headers of the route, they are kept when not provided
+    headers: Option<Vec<Header>>,
     key: Option<String>,
+    revision: Option<u64>,
 }
 
 
@@ -30,6 +38,7 @@ pub struct Request {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     manage_url: String,
+    revision: u64,
 }
 
 enum Error {
@@ -37,22 +46,39 @@ enum Error {
     DecodeDataUrl(forgiving_base64::InvalidBase64),
     ParseDomain(domain::Error),
     ParsePath(url_path::Error),
+    InvalidHeaders(headers::Error),
     NoKeyProvided(),
     VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
     GetSite(GetSiteError),
     InvalidKey(),
+    StaleRevision(),
     UpdateRoute(site::UpdateRouteError),
     PersistSite(site::PersistSiteError),
 }
 
-pub async fn handler(state: web::Data<AppState>, session: Session, request_data: web::Json<Request>) -> HttpResponse {
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+    let has_key = request_data.key.is_some();
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &blocking_session_data, &request_data), Error::Canceled()).await;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if has_key && result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
 
-    handle(&state, session, &request_data)
+    result
         .map(prepare_response)
         .unwrap_or_else(handle_error)
 }
 
-fn handle(state: &AppState, session: Session, request_data: &Request) -> Result<Site, Error> {
+fn handle(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, request_data: &Request) -> Result<Site, Error> {
     let domain = Domain::from_str(&request_data.domain)
         .map_err(Error::ParseDomain)?;
 
@@ -65,48 +91,57 @@ fn handle(state: &AppState, session: Session, request_data: &Request) -> Result<
     let (file_data, _) = url.decode_to_vec()
         .map_err(Error::DecodeDataUrl)?;
 
+    let route_headers = request_data.headers
+        .as_deref()
+        .map(headers::validate)
+        .transpose()
+        .map_err(Error::InvalidHeaders)?;
+
     let time = SystemTime::now();
     let mime_type = format!("{}", url.mime_type());
     let file_info = FileInfo::new(&file_data, mime_type, time);
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
 
     let mut site = site::get(&site_root)
         .map_err(Error::GetSite)?;
 
-    let mut session_data = SessionData::from_session(&session)
-        .unwrap_or_else(SessionData::new);
-
-    let provided_key = get_provided_key(&request_data, &session_data, &site.domain)
+    let provided_key = get_provided_key(bearer_token, request_data, session_data, &state.sessions, &site.domain)
         .ok_or(Error::NoKeyProvided())?;
 
-    let has_valid_key = site.key.verify(&provided_key)
+    let has_valid_key = provided_key.verify(&site, &Access::WriteRoutes(vec![&path]))
         .map_err(Error::VerifyKey)?;
 
     util::ensure(has_valid_key, Error::InvalidKey())?;
 
-    site.update_route(&state.config.site, path, file_info, &file_data)
+    util::ensure(site.is_expected_revision(request_data.revision), Error::StaleRevision())?;
+
+    site.update_route(&state.config.site, path.clone(), file_info, &file_data)
         .map_err(Error::UpdateRoute)?;
 
-    site.persist(&site_root)
-        .map_err(Error::PersistSite)?;
+    if let Some(route_headers) = route_headers {
+        site.update_route_headers(&path, route_headers)
+            .map_err(Error::UpdateRoute)?;
+    }
 
-    match &request_data.key {
-        Some(key) => {
-            let _ = session_data.add_site(&site, &state.config.site, key);
-            let _ = session_data.update_session(&session);
-        },
+    site.persist(&state.config.site, &site_root)
+        .map_err(Error::PersistSite)?;
 
-        None =>
-            ()
+    // The key was verified, the session can manage the site without it until the capability expires
+    if let Some(key) = &request_data.key {
+        let _ = session_data.add_site(&state.sessions, &site, &state.config.site, key);
     }
 
     Ok(site)
 }
 
-fn get_provided_key(request_data: &Request, session_data: &SessionData, domain: &Domain) -> Option<String> {
-    let key_from_session = session_data.get_site_key(domain);
-
-    request_data.key.clone().or(key_from_session)
+fn get_provided_key(bearer_token: Option<String>, request_data: &Request, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    request_data.key.clone()
+        .map(Credential::Key)
+        .or_else(|| bearer_token.map(Credential::Token))
+        .or_else(|| session_data.get_site_credential(sessions, domain))
 }
 
 
@@ -116,6 +151,7 @@ fn prepare_response(site: Site) -> HttpResponse {
     http_helper::no_cache_headers(&mut HttpResponse::Ok())
         .json(Response{
             manage_url: manage_route.to_string(),
+            revision: site.revision,
         })
 }
 
@@ -135,6 +171,22 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::ParsePath(err) =>
             handle_parse_path_error(err),
 
+        Error::InvalidHeaders(err) =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&err.to_string())),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
         Error::GetSite(err) =>
             handle_get_site_error(err),
 
@@ -146,6 +198,10 @@ fn handle_error(err: Error) -> HttpResponse {
             HttpResponse::Unauthorized()
                 .json(http::Error::from_str("Invalid key")),
 
+        Error::StaleRevision() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("The site has been changed by someone else, reload and try again")),
+
         Error::VerifyKey(err) => {
             log::error!("Failed to verify key: {:?}", err);
             HttpResponse::InternalServerError()
@@ -228,6 +284,11 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -264,5 +325,22 @@ fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
             HttpResponse::InternalServerError()
                 .json(http::Error::from_str("Failed to persist site"))
         },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
     }
 }
@@ -0,0 +1,214 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_session::Session;
+use serde::{Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, GetSiteError};
+use crate::orri::site::revision::{self, Revision, ListRevisionsError};
+use crate::orri::http;
+use crate::orri::domain::{self, Domain};
+use crate::orri::util;
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::http as http_helper;
+use std::str::FromStr;
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    revision: u64,
+    revisions: Vec<RevisionResponse>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionResponse {
+    revision: u64,
+    timestamp: u64,
+    route_count: usize,
+    size: usize,
+}
+
+struct ViewData {
+    site: Site,
+    revisions: Vec<Revision>,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    GetSite(GetSiteError),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    InvalidKey(),
+    ListRevisions(ListRevisionsError),
+    Canceled(),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &session_data, &domain), Error::Canceled()).await;
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, domain_str: &str) -> Result<ViewData, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    verify_read_access(bearer_token, state, session_data, &site)?;
+
+    let revisions = revision::list(&site_root)
+        .map_err(Error::ListRevisions)?;
+
+    Ok(ViewData{
+        site,
+        revisions,
+    })
+}
+
+// Revisions are only shown to collaborators, including viewers, and to tokens
+fn verify_read_access(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, site: &Site) -> Result<(), Error> {
+    let credential = bearer_token
+        .map(Credential::Token)
+        .or_else(|| session_data.get_site_credential(&state.sessions, &site.domain))
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_access = credential.verify(site, &Access::Read())
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_access, Error::InvalidKey())
+}
+
+
+fn prepare_response(view_data: ViewData) -> HttpResponse {
+    let revisions = view_data.revisions
+        .iter()
+        .map(|revision| RevisionResponse{
+            revision: revision.revision,
+            timestamp: revision.timestamp,
+            route_count: revision.routes.len(),
+            size: revision.size(),
+        })
+        .collect();
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            revision: view_data.site.revision,
+            revisions,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::ListRevisions(err) =>
+            handle_list_revisions_error(err),
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to read site"))
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_list_revisions_error(err: ListRevisionsError) -> HttpResponse {
+    match err {
+        ListRevisionsError::ReadDir(err) => {
+            log::error!("Failed to read revisions dir: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to list revisions"))
+        },
+
+        ListRevisionsError::ReadRevisionJson(err) => {
+            log::error!("Failed to read revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to list revisions"))
+        },
+    }
+}
@@ -0,0 +1,190 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::{Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, RouteInfo, GetSiteError};
+use crate::orri::site::headers::Header;
+use crate::orri::http;
+use crate::orri::domain::{self, Domain};
+use crate::orri::util;
+use crate::orri::api_token::{self, Access};
+use crate::orri::http as http_helper;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    revision: u64,
+    routes: Vec<RouteResponse>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteResponse {
+    path: String,
+    timestamp: u64,
+
+    #[serde(flatten)]
+    kind: RouteKindResponse,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RouteKindResponse {
+    File{
+        mime: String,
+        size: usize,
+        hash: String,
+        headers: Vec<Header>,
+    },
+
+    Redirect{
+        target: String,
+        status: u16,
+    },
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    GetSite(GetSiteError),
+    InvalidToken(),
+    Canceled(),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, domain: web::Path<String>) -> HttpResponse {
+    let bearer_token = http_helper::bearer_token(&req);
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &domain), Error::Canceled()).await;
+
+    result
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+fn handle(bearer_token: Option<String>, state: &AppState, domain_str: &str) -> Result<Site, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
+
+    // The read apis are public, a token is still checked so that a script with a wrong or expired token fails early
+    if let Some(token) = bearer_token {
+        util::ensure(api_token::is_allowed(&site.tokens, &token, &Access::Read(), SystemTime::now()), Error::InvalidToken())?;
+    }
+
+    Ok(site)
+}
+
+
+fn prepare_response(site: Site) -> HttpResponse {
+    let routes = site.routes
+        .iter()
+        .map(|(path, route_info)| RouteResponse{
+            path: path.to_string(),
+            timestamp: route_info.timestamp(),
+            kind: route_kind_response(route_info),
+        })
+        .collect();
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            revision: site.revision,
+            routes,
+        })
+}
+
+fn route_kind_response(route_info: &RouteInfo) -> RouteKindResponse {
+    match route_info {
+        RouteInfo::File{file_info, headers} =>
+            RouteKindResponse::File{
+                mime: file_info.mime.clone(),
+                size: file_info.size,
+                hash: file_info.hash.clone(),
+                headers: headers.clone(),
+            },
+
+        RouteInfo::Redirect{redirect} =>
+            RouteKindResponse::Redirect{
+                target: redirect.target.to_string(),
+                status: redirect.status.into(),
+            },
+    }
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::InvalidToken() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid token")),
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to read site"))
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
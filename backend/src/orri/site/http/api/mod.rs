@@ -3,3 +3,21 @@ pub mod remove_site;
 pub mod remove_route;
 pub mod add_route;
 pub mod edit_route;
+pub mod put_route;
+pub mod upload_route;
+pub mod deploy_site;
+pub mod apply_changes;
+pub mod list_revisions;
+pub mod diff_revisions;
+pub mod rollback_site;
+pub mod update_settings;
+pub mod add_redirect;
+pub mod edit_redirect;
+pub mod list_routes;
+pub mod add_session;
+pub mod revoke_sessions;
+pub mod create_token;
+pub mod revoke_token;
+pub mod add_collaborator;
+pub mod remove_collaborator;
+pub mod rotate_key;
@@ -1,5 +1,6 @@
 use std::time::SystemTime;
 use std::str::FromStr;
+use std::io;
 use actix_web::{web, HttpResponse};
 use actix_session::Session;
 use serde::{Deserialize, Serialize};
@@ -36,19 +37,33 @@ enum Error {
     DecodeDataUrl(forgiving_base64::InvalidBase64),
     ParseDomain(domain::Error),
     SiteKey(site_key::Error),
+    LockSite(io::Error),
+    Canceled(),
     CreateSite(CreateSiteError),
     PersistSite(site::PersistSiteError),
     SessionData(session_data::Error),
 }
 
 pub async fn handler(state: web::Data<AppState>, session: Session, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let blocking_state = state.clone();
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(&blocking_state, &blocking_session_data, &request_data), Error::Canceled()).await;
+
+    // The site was added to the session store, the cookie must hold the session id
+    if result.is_ok() {
+        let _ = session_data.update_session(&session);
+    }
 
-    handle(&state, &session, &request_data)
+    result
         .map(|site| prepare_response(&state.config, site))
         .unwrap_or_else(handle_error)
 }
 
-fn handle(state: &AppState, session: &Session, request_data: &Request) -> Result<Site, Error> {
+fn handle(state: &AppState, session_data: &SessionData, request_data: &Request) -> Result<Site, Error> {
     let domain = Domain::from_str(&request_data.domain)
         .map_err(Error::ParseDomain)?;
 
@@ -61,34 +76,27 @@ fn handle(state: &AppState, session: &Session, request_data: &Request) -> Result
     let time = SystemTime::now();
     let mime_type = format!("{}", url.mime_type());
     let file_info = FileInfo::new(&file_data, mime_type, time);
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
 
     let site_key = site_key::from_str(&state.config.site_key, &request_data.key)
         .map_err(Error::SiteKey)?;
 
-    let site = site::create(&state.config.site, &site_root, site_key, file_info, &file_data)
+    let mut site = site::create(&state.config.site, &site_root, site_key, file_info, &file_data)
         .map_err(Error::CreateSite)?;
 
-    site.persist(&site_root)
+    site.persist(&state.config.site, &site_root)
         .map_err(Error::PersistSite)?;
 
-    let mut session_data = SessionData::from_session(&session)
-        .unwrap_or_else(SessionData::new);
-
-    let session_data_result = session_data.add_site(&site, &state.config.site, &request_data.key)
+    let session_data_result = session_data.add_site(&state.sessions, &site, &state.config.site, &request_data.key)
         .map_err(Error::SessionData);
 
-    match session_data_result {
-        Ok(()) => {
-            let _ = session_data.update_session(&session);
-            Ok(())
-        },
-
-        Err(err) => {
-            let _ = site_root.remove();
-            Err(err)
-        },
-    }?;
+    if let Err(err) = session_data_result {
+        let _ = site_root.remove(&state.config.site);
+        return Err(err);
+    }
 
 
     Ok(site)
@@ -122,6 +130,18 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::SiteKey(err) =>
             handle_site_key_error(err),
 
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to create site"))
+        },
+
         Error::CreateSite(err) =>
             handle_create_site_error(err),
 
@@ -190,6 +210,12 @@ fn handle_site_key_error(err: site_key::Error) -> HttpResponse {
 
 fn handle_create_site_error(err: CreateSiteError) -> HttpResponse {
     match err {
+        CreateSiteError::CheckSiteExist(err) => {
+            log::error!("Failed to check if site exist: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to create site"))
+        },
+
         CreateSiteError::SiteAlreadyExist() => {
             HttpResponse::Conflict()
                 .json(http::Error::from_str("Site already exist"))
@@ -224,9 +250,22 @@ fn handle_session_data_error(err: session_data::Error) -> HttpResponse {
                 .json(http::Error::from_str("Max total sites reached"))
         },
 
-        session_data::Error::SessionDataTooLarge() => {
-            HttpResponse::BadRequest()
-                .json(http::Error::from_str("The session cookie is not able to store more sites"))
+        session_data::Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
+        },
+
+        session_data::Error::ReadSession(err) => {
+            log::error!("Failed to read session: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
+        },
+
+        session_data::Error::WriteSession(err) => {
+            log::error!("Failed to write session: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to update session"))
         },
     }
 }
@@ -250,5 +289,22 @@ fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
             HttpResponse::InternalServerError()
                 .json(http::Error::from_str("Failed to persist site"))
         },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
     }
 }
@@ -0,0 +1,452 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::error::PayloadError;
+use actix_session::Session;
+use serde::{Deserialize, Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, FileInfo, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::file;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::url_path::{self, UrlPath};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::route::Route;
+use crate::orri::http as http_helper;
+use actix_http::http::{header};
+use futures::StreamExt;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::io;
+
+
+#[derive(Deserialize)]
+pub struct QueryParams {
+    path: String,
+    revision: Option<u64>,
+}
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    manage_url: String,
+    revision: u64,
+}
+
+// The request is not Send, the credentials are read from it before the work moves to the thread pool
+#[derive(Clone)]
+struct ProvidedCredentials {
+    site_key: Option<String>,
+    bearer_token: Option<String>,
+    session_data: SessionData,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    ParsePath(url_path::Error),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    StaleRevision(),
+    PrepareDirectories(io::Error),
+    CreateTempFile(io::Error),
+    ReadPayload(PayloadError),
+    WriteTempFile(io::Error),
+    FileTooLarge(),
+    AddRoute(site::AddRouteError),
+    UpdateRoute(site::UpdateRouteError),
+    WriteFile(io::Error),
+    PersistSite(site::PersistSiteError),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>, query: web::Query<QueryParams>, payload: web::Payload) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let credentials = ProvidedCredentials{
+        site_key: site_key_header(&req),
+        bearer_token: http_helper::bearer_token(&req),
+        session_data,
+    };
+
+    let content_type = content_type_header(&req);
+
+    handle(credentials, state, &domain, query.into_inner(), content_type, payload).await
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+async fn handle(credentials: ProvidedCredentials, state: web::Data<AppState>, domain_str: &str, query: QueryParams, content_type: Option<String>, payload: web::Payload) -> Result<Site, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let path = UrlPath::from_str(&query.path)
+        .map_err(Error::ParsePath)?;
+
+    let blocking_state = state.clone();
+    let blocking_domain = domain.clone();
+    let blocking_path = path.clone();
+    let blocking_credentials = credentials.clone();
+
+    // Checked before the payload is read, the key is verified again under the site lock
+    let (site, writer) = http_helper::block(move || {
+        let site_root = site::SiteRoot::new(&blocking_state.storage, blocking_domain);
+
+        let site = site::get(&site_root)
+            .map_err(Error::GetSite)?;
+
+        verify_key(&blocking_credentials, &blocking_state, &site, &blocking_path)?;
+
+        site_root.prepare_directories()
+            .map_err(Error::PrepareDirectories)?;
+
+        let writer = file::HashWriter::new(&site_root.blob_store().temp_path())
+            .map_err(Error::CreateTempFile)?;
+
+        Ok((site, writer))
+    }, Error::Canceled()).await?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+    let max_size = site.quota.limits(&state.config.site).max_size;
+
+    let writer = write_payload(payload, writer, max_size).await?;
+
+    let hashed_file = writer.finish();
+    let mime_type = content_type.unwrap_or_else(|| guess_mime_type(&path));
+
+    http_helper::block(move || save_route(&credentials, &state, &site_root, &query, path, mime_type, hashed_file), Error::Canceled()).await
+}
+
+// The site is read again while holding the lock since it may have changed during the upload
+fn save_route(credentials: &ProvidedCredentials, state: &AppState, site_root: &site::SiteRoot, query: &QueryParams, path: UrlPath, mime_type: String, hashed_file: file::HashedFile) -> Result<Site, Error> {
+    let _lock = state.site_locks.lock(site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(site_root)
+        .map_err(Error::GetSite)?;
+
+    verify_key(credentials, state, &site, &path)?;
+
+    util::ensure(site.is_expected_revision(query.revision), Error::StaleRevision())?;
+
+    let time = SystemTime::now();
+    let file_info = FileInfo::from_hashed_file(&hashed_file, mime_type, time);
+
+    if site.routes.contains_key(&path) {
+        site.update_uploaded_route(&state.config.site, path, file_info)
+            .map_err(Error::UpdateRoute)?;
+    } else {
+        site.add_uploaded_route(&state.config.site, path, file_info)
+            .map_err(Error::AddRoute)?;
+    }
+
+    site_root.blob_store().persist(hashed_file)
+        .map_err(Error::WriteFile)?;
+
+    site.persist(&state.config.site, site_root)
+        .map_err(Error::PersistSite)?;
+
+    Ok(site)
+}
+
+fn verify_key(credentials: &ProvidedCredentials, state: &AppState, site: &Site, path: &UrlPath) -> Result<(), Error> {
+    let provided_key = credentials.get_provided_key(&state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(site, &Access::WriteRoutes(vec![path]))
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())
+}
+
+// The writer is moved to the thread pool for each chunk and handed back when the chunk is written
+async fn write_payload(mut payload: web::Payload, mut writer: file::HashWriter, max_size: usize) -> Result<file::HashWriter, Error> {
+    while let Some(chunk) = payload.next().await {
+        let bytes = chunk.map_err(Error::ReadPayload)?;
+
+        util::ensure(writer.size() + bytes.len() < max_size, Error::FileTooLarge())?;
+
+        writer = http_helper::block(move || {
+            writer.write(&bytes)
+                .map_err(Error::WriteTempFile)?;
+
+            Ok(writer)
+        }, Error::Canceled()).await?;
+    }
+
+    Ok(writer)
+}
+
+fn site_key_header(req: &HttpRequest) -> Option<String> {
+    req.headers().get(http_helper::SITE_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+impl ProvidedCredentials {
+    fn get_provided_key(&self, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+        self.site_key.clone()
+            .map(Credential::Key)
+            .or_else(|| self.bearer_token.clone().map(Credential::Token))
+            .or_else(|| self.session_data.get_site_credential(sessions, domain))
+    }
+}
+
+fn content_type_header(req: &HttpRequest) -> Option<String> {
+    req.headers().get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+fn guess_mime_type(path: &UrlPath) -> String {
+    mime_guess::from_path(path.relative_path())
+        .first_or_octet_stream()
+        .to_string()
+}
+
+
+fn prepare_response(site: Site) -> HttpResponse {
+    let manage_route = Route::ManageSite(site.domain.to_string());
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            manage_url: manage_route.to_string(),
+            revision: site.revision,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::ParsePath(err) =>
+            handle_parse_path_error(err),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::StaleRevision() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("The site has been changed by someone else, reload and try again")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::PrepareDirectories(err) => {
+            log::error!("Failed to prepare site directories: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::CreateTempFile(err) => {
+            log::error!("Failed to create temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::ReadPayload(err) => {
+            log::error!("Failed to read payload: {}", err);
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Failed to read request body"))
+        },
+
+        Error::WriteTempFile(err) => {
+            log::error!("Failed to write temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::FileTooLarge() =>
+            HttpResponse::PayloadTooLarge()
+                .json(http::Error::from_str("Max total size reached")),
+
+        Error::AddRoute(err) => {
+            handle_failed_to_add_route(err)
+        },
+
+        Error::UpdateRoute(err) => {
+            handle_failed_to_update_route(err)
+        },
+
+        Error::WriteFile(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_parse_path_error(err: url_path::Error) -> HttpResponse {
+    match err {
+        url_path::Error::MustStartWithSlash() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path must start with a slash")),
+
+        url_path::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path is too long")),
+
+        url_path::Error::ContainsDisallowedChars() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path contains disallowed characters")),
+
+        url_path::Error::ContainsDoubleDot() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path cannot contain double dots")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_failed_to_add_route(err: site::AddRouteError) -> HttpResponse {
+    match err {
+        site::AddRouteError::QuotaMaxSize() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total size reached"))
+        },
+
+        site::AddRouteError::QuotaMaxRoutes() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max routes reached"))
+        },
+    }
+}
+
+fn handle_failed_to_update_route(err: site::UpdateRouteError) -> HttpResponse {
+    match err {
+        site::UpdateRouteError::RouteNotFound() => {
+            HttpResponse::NotFound()
+                .json(http::Error::from_str("Route not found"))
+        },
+
+        site::UpdateRouteError::QuotaMaxSize() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total size reached"))
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
@@ -1,4 +1,4 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use actix_session::Session;
 use serde::{Deserialize};
 use crate::orri::app_state::AppState;
@@ -7,7 +7,10 @@ use crate::orri::http;
 use crate::orri::util;
 use crate::orri::domain::{self, Domain};
 use crate::orri::site_key;
-use crate::orri::session_data::{SessionData};
+use crate::orri::api_token::Access;
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::file::WriteJsonError;
 use crate::orri::http as http_helper;
 use std::str::FromStr;
 use std::io;
@@ -24,51 +27,65 @@ enum Error {
     ParseDomain(domain::Error),
     NoKeyProvided(),
     VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
     GetSite(GetSiteError),
     InvalidKey(),
+    RevokeSessions(WriteJsonError),
     RemoveSite(io::Error),
 }
 
-pub async fn handler(state: web::Data<AppState>, session: Session, request_data: web::Json<Request>) -> HttpResponse {
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, request_data: web::Json<Request>) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+    let blocking_session_data = session_data.clone();
+
+    let result = http_helper::block(move || handle(bearer_token, &state, &blocking_session_data, &request_data), Error::Canceled()).await;
 
-    handle(&state, session, &request_data)
+    result
         .map(prepare_response)
         .unwrap_or_else(handle_error)
 }
 
-fn handle(state: &AppState, session: Session, request_data: &Request) -> Result<Site, Error> {
+fn handle(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, request_data: &Request) -> Result<Site, Error> {
     let domain = Domain::from_str(&request_data.domain)
         .map_err(Error::ParseDomain)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let _lock = state.site_locks.lock(&site_root)
+        .map_err(Error::LockSite)?;
 
     let site = site::get(&site_root)
         .map_err(Error::GetSite)?;
 
-    let mut session_data = SessionData::from_session(&session)
-        .unwrap_or_else(SessionData::new);
-
-    let provided_key = get_provided_key(&request_data, &session_data, &site.domain)
+    let provided_key = get_provided_key(bearer_token, request_data, session_data, &state.sessions, &site.domain)
         .ok_or(Error::NoKeyProvided())?;
 
-    let has_valid_key = site.key.verify(&provided_key)
+    let has_valid_key = provided_key.verify(&site, &Access::DeleteSite())
         .map_err(Error::VerifyKey)?;
 
     util::ensure(has_valid_key, Error::InvalidKey())?;
 
-    site_root.remove()
+    // Sessions must not keep access to a new site that is created with the same domain
+    state.sessions.revoke_site(&site.domain)
+        .map_err(Error::RevokeSessions)?;
+
+    site_root.remove(&state.config.site)
         .map_err(Error::RemoveSite)?;
 
-    session_data.remove_site(&site.domain);
-    let _ = session_data.update_session(&session);
+    let _ = session_data.remove_site(&state.sessions, &site.domain);
 
     Ok(site)
 }
 
-fn get_provided_key(request_data: &Request, session_data: &SessionData, domain: &Domain) -> Option<String> {
-    let key_from_session = session_data.get_site_key(domain);
-
-    request_data.key.clone().or(key_from_session)
+fn get_provided_key(bearer_token: Option<String>, request_data: &Request, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    request_data.key.clone()
+        .map(Credential::Key)
+        .or_else(|| bearer_token.map(Credential::Token))
+        .or_else(|| session_data.get_site_credential(sessions, domain))
 }
 
 
@@ -82,6 +99,18 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::ParseDomain(err) =>
             handle_parse_domain_error(err),
 
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
         Error::GetSite(err) =>
             handle_get_site_error(err),
 
@@ -99,6 +128,12 @@ fn handle_error(err: Error) -> HttpResponse {
                 .json(http::Error::from_str("Failed to verify key"))
         },
 
+        Error::RevokeSessions(err) => {
+            log::error!("Failed to revoke sessions: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to remove site"))
+        },
+
         Error::RemoveSite(err) => {
             log::error!("Failed to remove site: {}", err);
             HttpResponse::InternalServerError()
@@ -153,5 +188,10 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
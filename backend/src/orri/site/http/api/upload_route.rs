@@ -0,0 +1,520 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_session::Session;
+use actix_multipart::{Multipart, Field, MultipartError};
+use serde::{Serialize};
+use crate::orri::app_state::AppState;
+use crate::orri::site::{self, Site, FileInfo, GetSiteError};
+use crate::orri::http;
+use crate::orri::util;
+use crate::orri::file;
+use crate::orri::domain::{self, Domain};
+use crate::orri::site_key;
+use crate::orri::api_token::Access;
+use crate::orri::url_path::{self, UrlPath};
+use crate::orri::session_data::{SessionData, Credential};
+use crate::orri::session_store::SessionStore;
+use crate::orri::route::Route;
+use crate::orri::http as http_helper;
+use futures::StreamExt;
+use std::time::SystemTime;
+use std::str::FromStr;
+use std::io;
+
+
+const MAX_TEXT_FIELD_SIZE: usize = 1024;
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    manage_url: String,
+    revision: u64,
+}
+
+// The path, key and revision fields must be sent before the file field,
+// so the key can be verified before the file is streamed to disk
+#[derive(Default)]
+struct FormData {
+    path: Option<String>,
+    key: Option<String>,
+    revision: Option<u64>,
+}
+
+struct Upload {
+    path: UrlPath,
+    key: Option<String>,
+    revision: Option<u64>,
+    hashed_file: file::HashedFile,
+    mime: String,
+}
+
+enum Error {
+    ParseDomain(domain::Error),
+    ParsePath(url_path::Error),
+    NoKeyProvided(),
+    VerifyKey(site_key::VerifyError),
+    LockSite(io::Error),
+    Canceled(),
+    GetSite(GetSiteError),
+    InvalidKey(),
+    StaleRevision(),
+    RouteAlreadyExist(),
+    ReadMultipart(MultipartError),
+    FieldTooLarge(),
+    InvalidUtf8(),
+    InvalidRevision(),
+    NoPathProvided(),
+    NoFileProvided(),
+    PrepareDirectories(io::Error),
+    CreateTempFile(io::Error),
+    WriteTempFile(io::Error),
+    FileTooLarge(),
+    AddRoute(site::AddRouteError),
+    WriteFile(io::Error),
+    PersistSite(site::PersistSiteError),
+}
+
+pub async fn handler(req: HttpRequest, state: web::Data<AppState>, session: Session, domain: web::Path<String>, multipart: Multipart) -> HttpResponse {
+    let session_data = SessionData::from_session(&session)
+        .unwrap_or_else(SessionData::new);
+
+    let bearer_token = http_helper::bearer_token(&req);
+
+    handle(bearer_token, state, &session, session_data, &domain, multipart).await
+        .map(prepare_response)
+        .unwrap_or_else(handle_error)
+}
+
+async fn handle(bearer_token: Option<String>, state: web::Data<AppState>, session: &Session, session_data: SessionData, domain_str: &str, multipart: Multipart) -> Result<Site, Error> {
+    let domain = Domain::from_str(domain_str)
+        .map_err(Error::ParseDomain)?;
+
+    let site_root = site::SiteRoot::new(&state.storage, domain.clone());
+    let blocking_site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let site = http_helper::block(move || site::get(&blocking_site_root).map_err(Error::GetSite), Error::Canceled()).await?;
+
+    let upload = read_multipart(bearer_token.clone(), &state, multipart, &site, &session_data).await?;
+
+    let has_key = upload.key.is_some();
+    let blocking_session_data = session_data.clone();
+
+    let site = http_helper::block(move || save_upload(bearer_token, &state, &blocking_session_data, &site_root, upload), Error::Canceled()).await?;
+
+    // The key was added to the session store, the cookie must hold the session id
+    if has_key {
+        let _ = session_data.update_session(session);
+    }
+
+    Ok(site)
+}
+
+// The site is read again while holding the lock since it may have changed during the upload
+fn save_upload(bearer_token: Option<String>, state: &AppState, session_data: &SessionData, site_root: &site::SiteRoot, upload: Upload) -> Result<Site, Error> {
+    let _lock = state.site_locks.lock(site_root)
+        .map_err(Error::LockSite)?;
+
+    let mut site = site::get(site_root)
+        .map_err(Error::GetSite)?;
+
+    util::ensure(!site.routes.contains_key(&upload.path), Error::RouteAlreadyExist())?;
+
+    let provided_key = get_provided_key(bearer_token, upload.key.clone(), session_data, &state.sessions, &site.domain)
+        .ok_or(Error::NoKeyProvided())?;
+
+    let has_valid_key = provided_key.verify(&site, &Access::WriteRoutes(vec![&upload.path]))
+        .map_err(Error::VerifyKey)?;
+
+    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+    util::ensure(site.is_expected_revision(upload.revision), Error::StaleRevision())?;
+
+    let time = SystemTime::now();
+    let file_info = FileInfo::from_hashed_file(&upload.hashed_file, upload.mime, time);
+
+    site.add_uploaded_route(&state.config.site, upload.path, file_info)
+        .map_err(Error::AddRoute)?;
+
+    site_root.blob_store().persist(upload.hashed_file)
+        .map_err(Error::WriteFile)?;
+
+    site.persist(&state.config.site, site_root)
+        .map_err(Error::PersistSite)?;
+
+    // The key was verified, the session can manage the site without it until the capability expires
+    if let Some(key) = &upload.key {
+        let _ = session_data.add_site(&state.sessions, &site, &state.config.site, key);
+    }
+
+    Ok(site)
+}
+
+async fn read_multipart(bearer_token: Option<String>, state: &web::Data<AppState>, mut multipart: Multipart, site: &Site, session_data: &SessionData) -> Result<Upload, Error> {
+    let mut form_data = FormData::default();
+
+    while let Some(item) = multipart.next().await {
+        let field = item.map_err(Error::ReadMultipart)?;
+
+        let name = field.content_disposition()
+            .and_then(|content_disposition| content_disposition.get_name().map(|name| name.to_string()))
+            .unwrap_or_default();
+
+        match name.as_str() {
+            "path" => {
+                form_data.path = Some(read_text_field(field).await?);
+            },
+
+            "key" => {
+                form_data.key = Some(read_text_field(field).await?)
+                    .filter(|key| !key.is_empty());
+            },
+
+            "revision" => {
+                let revision = read_text_field(field).await?
+                    .parse::<u64>()
+                    .map_err(|_| Error::InvalidRevision())?;
+
+                form_data.revision = Some(revision);
+            },
+
+            "file" => {
+                let path_str = form_data.path.clone()
+                    .ok_or(Error::NoPathProvided())?;
+
+                let path = UrlPath::from_str(&path_str)
+                    .map_err(Error::ParsePath)?;
+
+                util::ensure(!site.routes.contains_key(&path), Error::RouteAlreadyExist())?;
+
+                let blocking_state = state.clone();
+                let blocking_site = site.clone();
+                let blocking_path = path.clone();
+                let blocking_session_data = session_data.clone();
+                let blocking_key = form_data.key.clone();
+
+                // Checked before the file is streamed to disk, the key is verified again under the site lock
+                let writer = http_helper::block(move || {
+                    let provided_key = get_provided_key(bearer_token, blocking_key, &blocking_session_data, &blocking_state.sessions, &blocking_site.domain)
+                        .ok_or(Error::NoKeyProvided())?;
+
+                    let has_valid_key = provided_key.verify(&blocking_site, &Access::WriteRoutes(vec![&blocking_path]))
+                        .map_err(Error::VerifyKey)?;
+
+                    util::ensure(has_valid_key, Error::InvalidKey())?;
+
+                    let site_root = site::SiteRoot::new(&blocking_state.storage, blocking_site.domain);
+
+                    site_root.prepare_directories()
+                        .map_err(Error::PrepareDirectories)?;
+
+                    file::HashWriter::new(&site_root.blob_store().temp_path())
+                        .map_err(Error::CreateTempFile)
+                }, Error::Canceled()).await?;
+
+                let mime = field.content_type().to_string();
+                let max_size = site.quota.limits(&state.config.site).max_size;
+                let hashed_file = write_file_field(field, writer, max_size).await?;
+
+                return Ok(Upload{
+                    path,
+                    key: form_data.key,
+                    revision: form_data.revision,
+                    hashed_file,
+                    mime,
+                });
+            },
+
+            _ =>
+                (),
+        }
+    }
+
+    Err(Error::NoFileProvided())
+}
+
+fn get_provided_key(bearer_token: Option<String>, key: Option<String>, session_data: &SessionData, sessions: &SessionStore, domain: &Domain) -> Option<Credential> {
+    key
+        .map(Credential::Key)
+        .or_else(|| bearer_token.map(Credential::Token))
+        .or_else(|| session_data.get_site_credential(sessions, domain))
+}
+
+async fn read_text_field(mut field: Field) -> Result<String, Error> {
+    let mut data = Vec::new();
+
+    while let Some(chunk) = field.next().await {
+        let bytes = chunk.map_err(Error::ReadMultipart)?;
+
+        util::ensure(data.len() + bytes.len() <= MAX_TEXT_FIELD_SIZE, Error::FieldTooLarge())?;
+
+        data.extend_from_slice(&bytes);
+    }
+
+    String::from_utf8(data)
+        .map_err(|_| Error::InvalidUtf8())
+}
+
+// The writer is moved to the thread pool for each chunk and handed back when the chunk is written
+async fn write_file_field(mut field: Field, mut writer: file::HashWriter, max_size: usize) -> Result<file::HashedFile, Error> {
+    while let Some(chunk) = field.next().await {
+        let bytes = chunk.map_err(Error::ReadMultipart)?;
+
+        util::ensure(writer.size() + bytes.len() < max_size, Error::FileTooLarge())?;
+
+        writer = http_helper::block(move || {
+            writer.write(&bytes)
+                .map_err(Error::WriteTempFile)?;
+
+            Ok(writer)
+        }, Error::Canceled()).await?;
+    }
+
+    Ok(writer.finish())
+}
+
+
+fn prepare_response(site: Site) -> HttpResponse {
+    let manage_route = Route::ManageSite(site.domain.to_string());
+
+    http_helper::no_cache_headers(&mut HttpResponse::Ok())
+        .json(Response{
+            manage_url: manage_route.to_string(),
+            revision: site.revision,
+        })
+}
+
+fn handle_error(err: Error) -> HttpResponse {
+    match err {
+        Error::ParseDomain(err) =>
+            handle_parse_domain_error(err),
+
+        Error::ParsePath(err) =>
+            handle_parse_path_error(err),
+
+        Error::LockSite(err) => {
+            log::error!("Failed to lock site: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to lock site"))
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to change site"))
+        },
+
+        Error::GetSite(err) =>
+            handle_get_site_error(err),
+
+        Error::NoKeyProvided() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("No key provided")),
+
+        Error::InvalidKey() =>
+            HttpResponse::Unauthorized()
+                .json(http::Error::from_str("Invalid key")),
+
+        Error::StaleRevision() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("The site has been changed by someone else, reload and try again")),
+
+        Error::InvalidRevision() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The revision must be a number")),
+
+        Error::VerifyKey(err) => {
+            log::error!("Failed to verify key: {:?}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to verify key"))
+        },
+
+        Error::PrepareDirectories(err) => {
+            log::error!("Failed to prepare site directories: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::CreateTempFile(err) => {
+            log::error!("Failed to create temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::ReadMultipart(err) => {
+            log::error!("Failed to read multipart: {}", err);
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Failed to read form data"))
+        },
+
+        Error::FieldTooLarge() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Form field is too large")),
+
+        Error::InvalidUtf8() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Form field is not valid utf-8")),
+
+        Error::NoPathProvided() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("No path provided")),
+
+        Error::NoFileProvided() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("No file provided")),
+
+        Error::WriteTempFile(err) => {
+            log::error!("Failed to write temp file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::RouteAlreadyExist() =>
+            HttpResponse::Conflict()
+                .json(http::Error::from_str("Route already exists")),
+
+        Error::FileTooLarge() =>
+            HttpResponse::PayloadTooLarge()
+                .json(http::Error::from_str("Max total size reached")),
+
+        Error::AddRoute(err) => {
+            handle_failed_to_add_route(err)
+        },
+
+        Error::WriteFile(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to store file"))
+        },
+
+        Error::PersistSite(err) => {
+            handle_persist_site_error(err)
+        },
+    }
+}
+
+fn handle_parse_domain_error(err: domain::Error) -> HttpResponse {
+    match err {
+        domain::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain is too long")),
+
+        domain::Error::SubdomainTooShort() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The subdomain is too short")),
+
+        domain::Error::InvalidChar() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain can only contain alphanumeric characters and hyphens")),
+
+        domain::Error::InvalidHyphenPosition() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot start or end with a hyphen")),
+
+        domain::Error::EmptyDomainValue() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The domain cannot be empty")),
+
+        domain::Error::MissingSecondLevelDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A second level domain is required")),
+
+        domain::Error::MissingSubDomain() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("A sub domain is required")),
+
+        domain::Error::OnlyOneSubdomainAllowed() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Only one subdomain is allowed")),
+    }
+}
+
+fn handle_parse_path_error(err: url_path::Error) -> HttpResponse {
+    match err {
+        url_path::Error::MustStartWithSlash() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path must start with a slash")),
+
+        url_path::Error::TooLong() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path is too long")),
+
+        url_path::Error::ContainsDisallowedChars() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path contains disallowed characters")),
+
+        url_path::Error::ContainsDoubleDot() =>
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("The path cannot contain double dots")),
+    }
+}
+
+fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
+    match err {
+        GetSiteError::SiteNotFound() => {
+            HttpResponse::NotFound().finish()
+        },
+
+        GetSiteError::ReadSiteJson(err) => {
+            log::error!("Failed to read site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn handle_failed_to_add_route(err: site::AddRouteError) -> HttpResponse {
+    match err {
+        site::AddRouteError::QuotaMaxSize() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max total size reached"))
+        },
+
+        site::AddRouteError::QuotaMaxRoutes() => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str("Max routes reached"))
+        },
+    }
+}
+
+fn handle_persist_site_error(err: site::PersistSiteError) -> HttpResponse {
+    match err {
+        site::PersistSiteError::CreateDomainDir(err) => {
+            log::error!("Failed to create domain: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteFileError(err) => {
+            log::error!("Failed to write file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteSiteJsonError(err) => {
+            log::error!("Failed to write site json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::WriteRevisionJsonError(err) => {
+            log::error!("Failed to write revision json: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::ReadRulesFile(err) => {
+            log::error!("Failed to read rules file: {}", err);
+            HttpResponse::InternalServerError()
+                .json(http::Error::from_str("Failed to persist site"))
+        },
+
+        site::PersistSiteError::InvalidRules(errors) => {
+            HttpResponse::BadRequest()
+                .json(http::Error::from_str(&site::rules::format_errors(&errors)))
+        },
+    }
+}
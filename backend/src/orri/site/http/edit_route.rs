@@ -2,12 +2,13 @@ use actix_web::{web, HttpResponse};
 use actix_session::Session;
 use crate::orri::app_state::AppState;
 use crate::orri::domain::{self, Domain};
-use crate::orri::site::{self, Site, GetSiteError};
+use crate::orri::site::{self, Site, GetSiteError, RouteInfo};
 use crate::orri::slowhtml::html::Html;
 use crate::orri::slowhtml::html;
 use crate::orri::slowhtml::attributes as attrs;
 use crate::orri::page::{self, Page, Head};
 use crate::orri::route::Route;
+use crate::orri::redirect::Status;
 use crate::orri::url_path::{self, UrlPath};
 use crate::orri::session_data::{SessionData};
 use crate::orri::util;
@@ -32,6 +33,9 @@ struct RequestData {
 struct ViewData {
     site: Site,
     path: UrlPath,
+
+    // The session can change the site without entering the key
+    client_has_key: bool,
 }
 
 enum Error {
@@ -39,6 +43,7 @@ enum Error {
     ParsePath(url_path::Error),
     RouteDoesNotExist(),
     GetSite(GetSiteError),
+    Canceled(),
 }
 
 pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>, query: web::Query<QueryParams>) -> HttpResponse {
@@ -48,12 +53,16 @@ pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::
         path: query.path.clone(),
     };
 
-    handle(&state, &request_data)
-        .map(|view_data| prepare_response(view_data, &session))
+    let session_data = SessionData::from_session(&session);
+
+    let result = http_helper::block(move || handle(&state, session_data, &request_data), Error::Canceled()).await;
+
+    result
+        .map(prepare_response)
         .unwrap_or_else(handle_error)
 }
 
-fn handle(state: &AppState, request_data: &RequestData) -> Result<ViewData, Error> {
+fn handle(state: &AppState, session_data: Option<SessionData>, request_data: &RequestData) -> Result<ViewData, Error> {
 
     let domain = Domain::from_str(&request_data.domain)
         .map_err(Error::ParseDomain)?;
@@ -61,28 +70,28 @@ fn handle(state: &AppState, request_data: &RequestData) -> Result<ViewData, Erro
     let path = UrlPath::from_str(&request_data.path)
         .map_err(Error::ParsePath)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
 
     let site = site::get(&site_root)
         .map_err(Error::GetSite)?;
 
     util::ensure(site.routes.contains_key(&path), Error::RouteDoesNotExist())?;
 
+    let client_has_key = session_data
+        .and_then(|session_data| session_data.get_site_credential(&state.sessions, &site.domain))
+        .is_some();
+
     Ok(ViewData{
         site,
         path,
+        client_has_key,
     })
 }
 
 
-fn prepare_response(view_data: ViewData, session: &Session) -> HttpResponse {
-
-    let client_has_key = SessionData::from_session(session)
-        .and_then(|session_data| session_data.get_site_key(&view_data.site.domain))
-        .and_then(|key_from_session| view_data.site.key.verify(&key_from_session).ok())
-        .unwrap_or(false);
+fn prepare_response(view_data: ViewData) -> HttpResponse {
 
-    let html = build_page(&view_data, client_has_key).render();
+    let html = build_page(&view_data, view_data.client_has_key).render();
 
     http_helper::no_cache_headers(&mut HttpResponse::Ok())
         .set_header(header::CONTENT_TYPE, "text/html")
@@ -102,9 +111,31 @@ fn build_page(view_data: &ViewData, client_has_key: bool) -> Page {
 
 
 fn build_body(view_data: &ViewData, client_has_key: bool) -> Vec<Html> {
-    let edit_route = Route::EditRouteJson();
+    let edit_route = Route::PutRouteJson(view_data.site.domain.to_string());
+    let edit_redirect = Route::EditRedirectJson(view_data.site.domain.to_string());
     let delete_route = Route::DeleteRouteJson();
 
+    let redirect = match view_data.site.routes.get(&view_data.path) {
+        Some(RouteInfo::Redirect{redirect}) =>
+            Some(redirect),
+
+        _ =>
+            None,
+    };
+
+    let current_status = redirect
+        .map(|redirect| redirect.status)
+        .unwrap_or_default();
+
+    let status_options = Status::all()
+        .into_iter()
+        .map(|status| status_option(status, status == current_status))
+        .collect::<Vec<Html>>();
+
+    let target = redirect
+        .map(|redirect| redirect.target.to_string())
+        .unwrap_or_default();
+
     vec![
         page::navbar(
             page::breadcrumbs(&[
@@ -123,6 +154,9 @@ fn build_body(view_data: &ViewData, client_has_key: bool) -> Vec<Html> {
                             attrs::id("form"),
                             attrs::attribute_trusted_name("data-api-method", &edit_route.request_method().to_string()),
                             attrs::attribute_trusted_name("data-api-url", &edit_route.to_string()),
+                            attrs::attribute_trusted_name("data-api-encoding", "raw"),
+                            attrs::attribute_trusted_name("data-redirect-api-method", &edit_redirect.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-redirect-api-url", &edit_redirect.to_string()),
                         ], &[
                         html::div(&[attrs::class("form-group")], &[
                             html::label(&[attrs::class("form-label")], &[
@@ -162,6 +196,15 @@ fn build_body(view_data: &ViewData, client_has_key: bool) -> Vec<Html> {
                             ]),
                         ),
                         html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Type")]),
+                                html::select(&[attrs::class("form-select"), attrs::name("kind"), attrs::id("kind")], &[
+                                    kind_option("file", "File", redirect.is_none()),
+                                    kind_option("redirect", "Redirect", redirect.is_some()),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group"), attrs::id("file-group")], &[
                             html::label(&[attrs::class("form-label")], &[
                                 html::div(&[], &[html::text("File")]),
                                 html::input(&[
@@ -172,6 +215,30 @@ fn build_body(view_data: &ViewData, client_has_key: bool) -> Vec<Html> {
                                 ]),
                             ]),
                         ]),
+                        html::div(&[attrs::class("display-none"), attrs::id("redirect-group")], &[
+                            html::div(&[attrs::class("form-group")], &[
+                                html::label(&[attrs::class("form-label")], &[
+                                    html::div(&[], &[html::text("Redirect to")]),
+                                    html::input(&[
+                                        attrs::type_("text"),
+                                        attrs::class("form-input"),
+                                        attrs::name("target"),
+                                        attrs::id("target"),
+                                        attrs::placeholder("i.e. /new-page or https://example.com/"),
+                                        attrs::value(&target),
+                                    ]),
+                                    html::p(&[attrs::class("form-input-hint")], &[
+                                        html::text("A path on this site or an absolute url"),
+                                    ]),
+                                ]),
+                            ]),
+                            html::div(&[attrs::class("form-group")], &[
+                                html::label(&[attrs::class("form-label")], &[
+                                    html::div(&[], &[html::text("Status")]),
+                                    html::select(&[attrs::class("form-select"), attrs::name("status")], &status_options),
+                                ]),
+                            ]),
+                        ]),
                         html::div(&[attrs::class("form-group margin-top-20")], &[
                             html::button(
                                 &[
@@ -204,6 +271,27 @@ fn build_body(view_data: &ViewData, client_has_key: bool) -> Vec<Html> {
     ]
 }
 
+fn kind_option(value: &str, text: &str, selected: bool) -> Html {
+    let mut attributes = vec![attrs::value(value)];
+
+    if selected {
+        attributes.push(attrs::selected());
+    }
+
+    html::option(&attributes, &[html::text(text)])
+}
+
+fn status_option(status: Status, selected: bool) -> Html {
+    let text = format!("{} {}", status, status.status_code().canonical_reason().unwrap_or_default());
+    let mut attributes = vec![attrs::value(&status.to_string())];
+
+    if selected {
+        attributes.push(attrs::selected());
+    }
+
+    html::option(&attributes, &[html::text(&text)])
+}
+
 
 fn handle_error(err: Error) -> HttpResponse {
     match err {
@@ -221,6 +309,11 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::GetSite(err) => {
             handle_get_site_error(err)
         },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -255,6 +348,11 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -1,8 +1,15 @@
 use actix_web::{web, HttpResponse};
+use actix_session::Session;
 use crate::orri::app_state::{AppState};
 use crate::orri::domain::{self, Domain};
 use crate::orri::url_path::{UrlPath};
 use crate::orri::site::{self, Site, GetSiteError, RouteInfo};
+use crate::orri::site::revision::{self, Revision, ListRevisionsError};
+use crate::orri::site::settings::TrailingSlash;
+use crate::orri::site::headers;
+use crate::orri::api_token::{ApiToken, Scope, Access};
+use crate::orri::session_data::SessionData;
+use crate::orri::collaborator::{Collaborator, Role};
 use crate::orri::slowhtml::html::Html;
 use crate::orri::slowhtml::html;
 use crate::orri::slowhtml::attributes as attrs;
@@ -11,38 +18,70 @@ use crate::orri::route::Route;
 use crate::orri::util;
 use crate::orri::http as http_helper;
 use http::header;
+use actix_http::http::header::HttpDate;
 use std::str::FromStr;
-use std::time::SystemTime;
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
 
 
 enum Error {
     ParseDomain(domain::Error),
     GetSite(GetSiteError),
+    ListRevisions(ListRevisionsError),
+    Canceled(),
 }
 
+struct ViewData {
+    site: Site,
+    revisions: Vec<Revision>,
 
-pub async fn handler(state: web::Data<AppState>, domain: web::Path<String>) -> HttpResponse {
-    let base_url = &state.config.server.sites_base_url(&domain);
+    // Revisions, collaborators and tokens are only shown to a session that has logged in with a key of the site
+    can_read: bool,
+}
+
+
+pub async fn handler(state: web::Data<AppState>, session: Session, domain: web::Path<String>) -> HttpResponse {
+    let base_url = state.config.server.sites_base_url(&domain);
+    let session_data = SessionData::from_session(&session);
 
-    handle(&state, &domain)
-        .map(|site| prepare_response(site, base_url))
+    let result = http_helper::block(move || handle(&state, session_data, &domain), Error::Canceled()).await;
+
+    result
+        .map(|view_data| prepare_response(view_data, &base_url))
         .unwrap_or_else(handle_error)
 }
 
 
-fn handle(state: &AppState, domain_str: &str) -> Result<Site, Error> {
+fn handle(state: &AppState, session_data: Option<SessionData>, domain_str: &str) -> Result<ViewData, Error> {
     let domain = Domain::from_str(&domain_str)
         .map_err(Error::ParseDomain)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
+
+    let site = site::get(&site_root)
+        .map_err(Error::GetSite)?;
 
-    site::get(&site_root)
-        .map_err(Error::GetSite)
+    let can_read = session_data
+        .and_then(|session_data| session_data.get_site_credential(&state.sessions, &site.domain))
+        .map(|credential| credential.verify(&site, &Access::Read()).unwrap_or(false))
+        .unwrap_or(false);
+
+    let revisions = if can_read {
+        revision::list(&site_root)
+            .map_err(Error::ListRevisions)?
+    } else {
+        vec![]
+    };
+
+    Ok(ViewData{
+        site,
+        revisions,
+        can_read,
+    })
 }
 
 
-fn prepare_response(site: Site, base_url: &str) -> HttpResponse {
-    let html = build_page(&site, base_url).render();
+fn prepare_response(view_data: ViewData, base_url: &str) -> HttpResponse {
+    let html = build_page(&view_data, base_url).render();
 
     http_helper::no_cache_headers(&mut HttpResponse::Ok())
         .set_header(header::CONTENT_TYPE, "text/html")
@@ -58,6 +97,15 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::GetSite(err) => {
             handle_get_site_error(err)
         },
+
+        Error::ListRevisions(err) => {
+            handle_list_revisions_error(err)
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -72,22 +120,50 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
-fn build_page(site: &Site, base_url: &str) -> Page {
+fn handle_list_revisions_error(err: ListRevisionsError) -> HttpResponse {
+    match err {
+        ListRevisionsError::ReadDir(err) => {
+            log::error!("Failed to read revisions dir: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        ListRevisionsError::ReadRevisionJson(err) => {
+            log::error!("Failed to read revision json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+    }
+}
+
+fn build_page(view_data: &ViewData, base_url: &str) -> Page {
+    let site = &view_data.site;
+
     Page{
         head: Head{
             title: format!("Manage {} - orri", &site.domain),
             elements: vec![]
         },
-        body: build_body(site, base_url)
+        body: build_body(view_data, base_url)
     }
 }
 
-fn build_body(site: &Site, base_url: &str) -> Vec<Html> {
+fn build_body(view_data: &ViewData, base_url: &str) -> Vec<Html> {
+    let site = &view_data.site;
     let add_route_route = Route::AddRoute(site.domain.to_string());
     let delete_site_route = Route::DeleteSiteJson();
+    let revoke_sessions_route = Route::RevokeSessionsJson(site.domain.to_string());
+    let update_settings_route = Route::UpdateSettingsJson(site.domain.to_string());
+    let create_token_route = Route::CreateTokenJson(site.domain.to_string());
+    let add_collaborator_route = Route::AddCollaboratorJson(site.domain.to_string());
+    let rotate_key_route = Route::RotateKeyJson(site.domain.to_string());
+    let add_session_route = Route::AddSessionJson(site.domain.to_string());
 
     let now = SystemTime::now();
 
@@ -96,6 +172,23 @@ fn build_body(site: &Site, base_url: &str) -> Vec<Html> {
         .map(|(route, route_info)| table_row(site, route, route_info, base_url, now))
         .collect::<Vec<Html>>();
 
+    let revision_rows = view_data.revisions
+        .iter()
+        .map(|revision| revision_table_row(site, revision))
+        .collect::<Vec<Html>>();
+
+    let collaborator_rows = site.collaborators
+        .iter()
+        .filter(|_| view_data.can_read)
+        .map(|collaborator| collaborator_table_row(site, collaborator))
+        .collect::<Vec<Html>>();
+
+    let token_rows = site.tokens
+        .iter()
+        .filter(|_| view_data.can_read)
+        .map(|token| token_table_row(site, token, now))
+        .collect::<Vec<Html>>();
+
     vec![
         page::navbar(
             page::breadcrumbs(&[
@@ -142,6 +235,325 @@ fn build_body(site: &Site, base_url: &str) -> Vec<Html> {
                             ],
                             &[html::text("Remove site")]
                         ),
+                        html::button(
+                            &[
+                                attrs::id("revoke-sessions"),
+                                attrs::type_("button"),
+                                attrs::class("btn btn-lg"),
+                                attrs::attribute_trusted_name("data-api-method", &revoke_sessions_route.request_method().to_string()),
+                                attrs::attribute_trusted_name("data-api-url", &revoke_sessions_route.to_string()),
+                            ],
+                            &[html::text("Log out everywhere")]
+                        ),
+                    ]),
+                    html::form(
+                        &[
+                            attrs::id("add-session-form"),
+                            attrs::class_list(&[("margin-top-40", true), ("display-none", view_data.can_read)]),
+                            attrs::attribute_trusted_name("data-api-method", &add_session_route.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-api-url", &add_session_route.to_string()),
+                        ], &[
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Enter a key of the site to see its revisions, collaborators and API tokens")]),
+                                html::input(&[
+                                    attrs::type_("password"),
+                                    attrs::class("form-input"),
+                                    attrs::name("key"),
+                                    attrs::required(),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group margin-top-20")], &[
+                            html::button(
+                                &[
+                                    attrs::type_("submit"),
+                                    attrs::class("btn btn-primary btn-lg"),
+                                    attrs::id("add-session-submit-button"),
+                                ], &[html::text("Log in")]
+                            ),
+                        ]),
+                    ]),
+                    html::h5(&[attrs::class("margin-top-40")], &[html::text("Revisions")]),
+                    html::table(&[attrs::class("table")], &[
+                        html::thead(&[], &[
+                            html::tr(&[], &[
+                                html::th(&[], &[html::text("Revision")]),
+                                html::th(&[], &[html::text("Date")]),
+                                html::th(&[], &[html::text("Routes")]),
+                                html::th(&[], &[html::text("Size")]),
+                                html::th(&[], &[]),
+                                html::th(&[], &[]),
+                            ]),
+                        ]),
+                        html::tbody(&[], &revision_rows),
+                    ]),
+                    html::h5(&[attrs::class("margin-top-40")], &[html::text("Settings")]),
+                    html::form(
+                        &[
+                            attrs::id("settings-form"),
+                            attrs::attribute_trusted_name("data-api-method", &update_settings_route.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-api-url", &update_settings_route.to_string()),
+                        ], &[
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Trailing slash")]),
+                                html::select(&[attrs::class("form-select"), attrs::name("trailingSlash")], &[
+                                    trailing_slash_option(site, TrailingSlash::Ignore, "Serve /docs and /docs/"),
+                                    trailing_slash_option(site, TrailingSlash::Add, "Redirect /docs to /docs/"),
+                                    trailing_slash_option(site, TrailingSlash::Remove, "Redirect /docs/ to /docs"),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Not found page")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("notFoundPage"),
+                                    attrs::placeholder("/404.html"),
+                                    attrs::value(&optional_path(&site.settings.not_found_page)),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Error page")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("errorPage"),
+                                    attrs::placeholder("/500.html"),
+                                    attrs::value(&optional_path(&site.settings.error_page)),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Fallback route")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("fallbackRoute"),
+                                    attrs::placeholder("/index.html"),
+                                    attrs::value(&optional_path(&site.settings.fallback_route)),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Fallback exclusions")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("fallbackExclude"),
+                                    attrs::placeholder("/api/, .js, .css"),
+                                    attrs::value(&site.settings.fallback_exclude.to_string()),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Default headers")]),
+                                html::textarea(
+                                    &[
+                                        attrs::class("form-input"),
+                                        attrs::name("headers"),
+                                        attrs::rows("4"),
+                                        attrs::placeholder("Content-Security-Policy: default-src 'self'"),
+                                    ], &[html::text(&headers::format_lines(&site.settings.headers))]
+                                ),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group margin-top-20")], &[
+                            html::button(
+                                &[
+                                    attrs::type_("submit"),
+                                    attrs::class("btn btn-primary btn-lg"),
+                                    attrs::id("settings-submit-button"),
+                                ], &[html::text("Save settings")]
+                            ),
+                        ]),
+                    ]),
+                    html::h5(&[attrs::class("margin-top-40")], &[html::text("Site key")]),
+                    html::div(&[attrs::class("toast toast-success display-none"), attrs::id("new-site-key")], &[
+                        html::div(&[], &[html::text("This is your new site key, please save it. The old key, all API tokens and all sessions no longer work")]),
+                        html::input(&[
+                            attrs::type_("text"),
+                            attrs::class("form-input"),
+                            attrs::id("new-site-key-value"),
+                            attrs::readonly(),
+                        ]),
+                    ]),
+                    html::form(
+                        &[
+                            attrs::id("rotate-key-form"),
+                            attrs::attribute_trusted_name("data-api-method", &rotate_key_route.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-api-url", &rotate_key_route.to_string()),
+                        ], &[
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Current key")]),
+                                html::input(&[
+                                    attrs::type_("password"),
+                                    attrs::class("form-input"),
+                                    attrs::name("key"),
+                                    attrs::required(),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group margin-top-20")], &[
+                            html::button(
+                                &[
+                                    attrs::type_("submit"),
+                                    attrs::class("btn btn-error btn-lg"),
+                                    attrs::id("rotate-key-submit-button"),
+                                ], &[html::text("Rotate key")]
+                            ),
+                        ]),
+                    ]),
+                    html::h5(&[attrs::class("margin-top-40")], &[html::text("Collaborators")]),
+                    html::table(&[attrs::class("table")], &[
+                        html::thead(&[], &[
+                            html::tr(&[], &[
+                                html::th(&[], &[html::text("Name")]),
+                                html::th(&[], &[html::text("Role")]),
+                                html::th(&[], &[html::text("Added")]),
+                                html::th(&[], &[]),
+                            ]),
+                        ]),
+                        html::tbody(&[], &collaborator_rows),
+                    ]),
+                    html::div(&[attrs::class("toast toast-success display-none"), attrs::id("new-collaborator")], &[
+                        html::div(&[], &[html::text("Give this key to the collaborator, it will not be shown again")]),
+                        html::input(&[
+                            attrs::type_("text"),
+                            attrs::class("form-input"),
+                            attrs::id("new-collaborator-key"),
+                            attrs::readonly(),
+                        ]),
+                    ]),
+                    html::form(
+                        &[
+                            attrs::id("collaborator-form"),
+                            attrs::attribute_trusted_name("data-api-method", &add_collaborator_route.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-api-url", &add_collaborator_route.to_string()),
+                        ], &[
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Name")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("name"),
+                                    attrs::placeholder("Alex"),
+                                    attrs::required(),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Role")]),
+                                html::select(&[attrs::class("form-select"), attrs::name("role")], &[
+                                    html::option(&[attrs::value(&Role::Owner.to_string())], &[html::text("Owner, can also remove the site and manage keys")]),
+                                    html::option(&[attrs::value(&Role::Editor.to_string()), attrs::selected()], &[html::text("Editor, can change routes and settings")]),
+                                    html::option(&[attrs::value(&Role::Viewer.to_string())], &[html::text("Viewer, can see revisions, collaborators and tokens")]),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group margin-top-20")], &[
+                            html::button(
+                                &[
+                                    attrs::type_("submit"),
+                                    attrs::class("btn btn-primary btn-lg"),
+                                    attrs::id("collaborator-submit-button"),
+                                ], &[html::text("Invite")]
+                            ),
+                        ]),
+                    ]),
+                    html::h5(&[attrs::class("margin-top-40")], &[html::text("API tokens")]),
+                    html::table(&[attrs::class("table")], &[
+                        html::thead(&[], &[
+                            html::tr(&[], &[
+                                html::th(&[], &[html::text("Name")]),
+                                html::th(&[], &[html::text("Scope")]),
+                                html::th(&[], &[html::text("Path prefix")]),
+                                html::th(&[], &[html::text("Expires")]),
+                                html::th(&[], &[]),
+                            ]),
+                        ]),
+                        html::tbody(&[], &token_rows),
+                    ]),
+                    html::div(&[attrs::class("toast toast-success display-none"), attrs::id("new-token")], &[
+                        html::div(&[], &[html::text("Copy the token now, it will not be shown again")]),
+                        html::input(&[
+                            attrs::type_("text"),
+                            attrs::class("form-input"),
+                            attrs::id("new-token-value"),
+                            attrs::readonly(),
+                        ]),
+                    ]),
+                    html::form(
+                        &[
+                            attrs::id("token-form"),
+                            attrs::attribute_trusted_name("data-api-method", &create_token_route.request_method().to_string()),
+                            attrs::attribute_trusted_name("data-api-url", &create_token_route.to_string()),
+                        ], &[
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Name")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("name"),
+                                    attrs::placeholder("CI"),
+                                    attrs::required(),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Scope")]),
+                                html::select(&[attrs::class("form-select"), attrs::name("scope")], &[
+                                    html::option(&[attrs::value(&Scope::Read.to_string())], &[html::text("Read")]),
+                                    html::option(&[attrs::value(&Scope::WriteRoutes.to_string()), attrs::selected()], &[html::text("Write routes")]),
+                                    html::option(&[attrs::value(&Scope::DeleteSite.to_string())], &[html::text("Write routes and delete site")]),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Path prefix")]),
+                                html::input(&[
+                                    attrs::type_("text"),
+                                    attrs::class("form-input"),
+                                    attrs::name("pathPrefix"),
+                                    attrs::placeholder("/docs/"),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group")], &[
+                            html::label(&[attrs::class("form-label")], &[
+                                html::div(&[], &[html::text("Expires in days")]),
+                                html::input(&[
+                                    attrs::type_("number"),
+                                    attrs::class("form-input"),
+                                    attrs::name("expiresInDays"),
+                                    attrs::min("1"),
+                                    attrs::placeholder("Never"),
+                                ]),
+                            ]),
+                        ]),
+                        html::div(&[attrs::class("form-group margin-top-20")], &[
+                            html::button(
+                                &[
+                                    attrs::type_("submit"),
+                                    attrs::class("btn btn-primary btn-lg"),
+                                    attrs::id("token-submit-button"),
+                                ], &[html::text("Create token")]
+                            ),
+                        ]),
                     ]),
                 ]),
             ]),
@@ -154,17 +566,130 @@ fn build_body(site: &Site, base_url: &str) -> Vec<Html> {
 fn table_row(site: &Site, route: &UrlPath, route_info: &RouteInfo, base_url: &str, now: SystemTime) -> Html {
     let route_url = format!("{}{}", base_url, route);
     let edit_url = Route::EditRoute(site.domain.to_string(), Some(route.to_string())).to_string();
-    let age_in_seconds = util::unix_timestamp(now) - route_info.file_info.timestamp;
+    let age_in_seconds = util::unix_timestamp(now) - route_info.timestamp();
     let recently_added = site.routes.len() > 1 && age_in_seconds < 5;
 
+    let (kind, details) = match route_info {
+        RouteInfo::File{file_info, ..} =>
+            (file_info.mime.clone(), file_info.size.to_string()),
+
+        RouteInfo::Redirect{redirect} =>
+            (format!("Redirect {}", redirect.status), format!("to {}", redirect.target)),
+    };
+
     html::tr(&[attrs::class_list(&[("success-fade", recently_added)])], &[
         html::td(&[], &[
             html::a(&[attrs::href(&route_url)], &[html::text(&route.to_string())]),
         ]),
-        html::td(&[], &[html::text(&route_info.file_info.mime)]),
-        html::td(&[], &[html::text(&route_info.file_info.size.to_string())]),
+        html::td(&[], &[html::text(&kind)]),
+        html::td(&[], &[html::text(&details)]),
         html::td(&[], &[
             html::a(&[attrs::href(&edit_url)], &[html::text("Edit")]),
         ]),
     ])
 }
+
+fn trailing_slash_option(site: &Site, trailing_slash: TrailingSlash, text: &str) -> Html {
+    let mut attributes = vec![attrs::value(&trailing_slash.to_string())];
+
+    if site.settings.trailing_slash == trailing_slash {
+        attributes.push(attrs::selected());
+    }
+
+    html::option(&attributes, &[html::text(text)])
+}
+
+fn optional_path(path: &Option<UrlPath>) -> String {
+    path.as_ref()
+        .map(|path| path.to_string())
+        .unwrap_or_default()
+}
+
+fn collaborator_table_row(site: &Site, collaborator: &Collaborator) -> Html {
+    let remove_route = Route::RemoveCollaboratorJson(site.domain.to_string(), collaborator.id.clone());
+    let added = HttpDate::from(UNIX_EPOCH + Duration::from_secs(collaborator.created));
+
+    html::tr(&[], &[
+        html::td(&[], &[html::text(&collaborator.name)]),
+        html::td(&[], &[html::text(&collaborator.role.to_string())]),
+        html::td(&[], &[html::text(&added.to_string())]),
+        html::td(&[], &[
+            html::button(
+                &[
+                    attrs::type_("button"),
+                    attrs::class("btn btn-sm remove-collaborator"),
+                    attrs::attribute_trusted_name("data-api-method", &remove_route.request_method().to_string()),
+                    attrs::attribute_trusted_name("data-api-url", &remove_route.to_string()),
+                ],
+                &[html::text("Revoke")]
+            ),
+        ]),
+    ])
+}
+
+fn token_table_row(site: &Site, token: &ApiToken, now: SystemTime) -> Html {
+    let revoke_route = Route::RevokeTokenJson(site.domain.to_string(), token.id.clone());
+
+    let path_prefix = optional_path(&token.path_prefix);
+
+    let expires = match token.expires {
+        Some(_) if token.is_expired(now) =>
+            "Expired".to_string(),
+
+        Some(expires) =>
+            HttpDate::from(UNIX_EPOCH + Duration::from_secs(expires)).to_string(),
+
+        None =>
+            "Never".to_string(),
+    };
+
+    html::tr(&[], &[
+        html::td(&[], &[html::text(&token.name)]),
+        html::td(&[], &[html::text(&token.scope.to_string())]),
+        html::td(&[], &[html::text(&path_prefix)]),
+        html::td(&[], &[html::text(&expires)]),
+        html::td(&[], &[
+            html::button(
+                &[
+                    attrs::type_("button"),
+                    attrs::class("btn btn-sm revoke-token"),
+                    attrs::attribute_trusted_name("data-api-method", &revoke_route.request_method().to_string()),
+                    attrs::attribute_trusted_name("data-api-url", &revoke_route.to_string()),
+                ],
+                &[html::text("Revoke")]
+            ),
+        ]),
+    ])
+}
+
+fn revision_table_row(site: &Site, revision: &Revision) -> Html {
+    let is_current = revision.revision == site.revision;
+    let diff_url = format!("{}?from={}&to={}", Route::DiffRevisionsJson(site.domain.to_string()), revision.revision, site.revision);
+    let rollback_route = Route::RollbackSiteJson(site.domain.to_string(), revision.revision.to_string());
+    let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(revision.timestamp));
+
+    let rollback_button = if is_current {
+        html::text("Current")
+    } else {
+        html::button(
+            &[
+                attrs::type_("button"),
+                attrs::class("btn btn-sm rollback-revision"),
+                attrs::attribute_trusted_name("data-api-method", &rollback_route.request_method().to_string()),
+                attrs::attribute_trusted_name("data-api-url", &rollback_route.to_string()),
+            ],
+            &[html::text("Rollback")]
+        )
+    };
+
+    html::tr(&[], &[
+        html::td(&[], &[html::text(&revision.revision.to_string())]),
+        html::td(&[], &[html::text(&date.to_string())]),
+        html::td(&[], &[html::text(&revision.routes.len().to_string())]),
+        html::td(&[], &[html::text(&revision.size().to_string())]),
+        html::td(&[], &[
+            html::a(&[attrs::href(&diff_url)], &[html::text("Diff")]),
+        ]),
+        html::td(&[], &[rollback_button]),
+    ])
+}
@@ -16,28 +16,33 @@ pub async fn handler(state: web::Data<AppState>, session: Session) -> HttpRespon
     let session_data = SessionData::from_session(&session)
         .unwrap_or_else(SessionData::new);
 
+    let blocking_state = state.clone();
 
-    let html = build_page(&state.config.server, session_data).render();
+    // The session store is read on the thread pool, list_sites logs its own errors
+    let domains = http_helper::block(move || Ok(session_data.list_sites(&blocking_state.sessions)), ())
+        .await
+        .unwrap_or_default();
+
+    let html = build_page(&state.config.server, &domains).render();
 
     http_helper::no_cache_headers(&mut HttpResponse::Ok())
         .set_header(header::CONTENT_TYPE, "text/html")
         .body(html)
 }
 
-fn build_page(server_config: &ServerConfig, session_data: SessionData) -> Page {
+fn build_page(server_config: &ServerConfig, domains: &[Domain]) -> Page {
     Page{
         head: Head{
             title: "My sites - orri".to_string(),
             elements: vec![],
         },
-        body: build_body(server_config, session_data)
+        body: build_body(server_config, domains)
     }
 }
 
 
-fn build_body(server_config: &ServerConfig, session_data: SessionData) -> Vec<Html> {
-    let rows = session_data
-        .list_sites()
+fn build_body(server_config: &ServerConfig, domains: &[Domain]) -> Vec<Html> {
+    let rows = domains
         .iter()
         .map(|domain| table_row(server_config, domain))
         .collect::<Vec<Html>>();
@@ -10,11 +10,14 @@ use std::str::FromStr;
 enum Error {
     ParseDomain(domain::Error),
     GetSite(GetSiteError),
+    Canceled(),
 }
 
 
 pub async fn handler(state: web::Data<AppState>, domain: web::Path<String>) -> HttpResponse {
-    handle(&state, &domain)
+    let result = http_helper::block(move || handle(&state, &domain), Error::Canceled()).await;
+
+    result
         .map(prepare_response)
         .unwrap_or_else(handle_error)
 }
@@ -24,7 +27,7 @@ fn handle(state: &AppState, domain_str: &str) -> Result<Site, Error> {
     let domain = Domain::from_str(&domain_str)
         .map_err(Error::ParseDomain)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
 
     site::get(&site_root)
         .map_err(Error::GetSite)
@@ -46,6 +49,11 @@ fn handle_error(err: Error) -> HttpResponse {
         Error::GetSite(err) => {
             handle_get_site_error(err)
         },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -60,5 +68,10 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
@@ -2,11 +2,22 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use crate::orri::app_state::AppState;
 use crate::orri::domain::{self, Domain};
 use crate::orri::url_path::{self, UrlPath};
-use crate::orri::site::{self, GetSiteError, File};
-use crate::orri::http as http_helper;
+use crate::orri::site::{self, Site, SiteRoot, GetSiteError, FileStream, FileInfo, RouteInfo, ResolvedRoute};
+use crate::orri::site::headers::Header;
 use crate::orri::http::{Host};
-use actix_http::http::{header};
-use std::io;
+use crate::orri::http as http_helper;
+use crate::orri::http::cache::{self, CachePolicy, Validators};
+use crate::orri::http::range::{self, ByteRange};
+use crate::orri::http::accept_encoding;
+use crate::orri::compression::{self, Encoding};
+use crate::orri::storage::{self, ObjectReader};
+use crate::orri::http::stream_body;
+use actix_web::dev::HttpResponseBuilder;
+use crate::orri::encryption_key;
+use actix_http::http::{header, StatusCode};
+use actix_http::http::HeaderMap;
+use actix_http::http::header::{HeaderName, HeaderValue};
+use std::io::{self, Read};
 use std::str::FromStr;
 
 
@@ -16,47 +27,342 @@ enum Error {
     GetSite(GetSiteError),
     RouteNotFound(),
     ReadRouteData(io::Error),
+    RangeNotSatisfiable(u64),
+    Canceled(),
+}
+
+enum Response {
+    Redirect(StatusCode, String),
+    ErrorPage(StatusCode, FileStream),
+    NotModified(Validators),
+    File(Validators, Option<Encoding>, FileStream),
+    PartialFile(Validators, FileInfo, ByteRange, ObjectReader),
+    MultipartFile(Validators, String, ObjectReader),
+}
+
+
+// The parts of the request that are needed to serve it, so it can be served on the thread pool
+struct SiteRequest {
+    host: String,
+    path: String,
+    query: Option<String>,
+    headers: HeaderMap,
+}
+
+impl SiteRequest {
+    fn new(req: &HttpRequest) -> SiteRequest {
+        let extensions = req.extensions();
+        let maybe_host: Option<&Host> = extensions.get();
+        let host = maybe_host
+            .map(|host| host.0.to_str().unwrap_or_default())
+            .unwrap_or("");
+
+        SiteRequest{
+            host: host.to_string(),
+            path: req.uri().path().to_string(),
+            query: req.uri().query().map(|query| query.to_string()),
+            headers: req.headers().clone(),
+        }
+    }
 }
 
 
 pub async fn handler(req: HttpRequest, state: web::Data<AppState>) -> HttpResponse {
+    let site_request = SiteRequest::new(&req);
+    let blocking_state = state.clone();
 
-    handle(&req, &state)
-        .map(prepare_response)
+    // Reading site.json and opening files blocks when the storage is remote
+    let result = http_helper::block(move || handle(&site_request, &blocking_state), Error::Canceled()).await;
+
+    result
+        .map(|(response, site_headers)| {
+            let mut http_response = prepare_response(&state.config.site_cache_policy, response);
+            add_site_headers(&mut http_response, site_headers);
+            http_response
+        })
         .unwrap_or_else(handle_error)
 }
 
 
-fn handle(req: &HttpRequest, state: &AppState) -> Result<File, Error> {
-    let extensions = req.extensions();
-    let maybe_host: Option<&Host> = extensions.get();
-    let host_str = maybe_host
-        .map(|host| host.0.to_str().unwrap_or_default())
-        .unwrap_or("");
-
-    let domain = Domain::from_str(host_str)
+// The response is returned with the headers the site has configured for the path
+fn handle(req: &SiteRequest, state: &AppState) -> Result<(Response, Vec<Header>), Error> {
+    let domain = Domain::from_str(&req.host)
         .map_err(Error::ParseDomain)?;
 
 
-    let path = UrlPath::from_str(req.uri().path())
+    let path = UrlPath::from_str(&req.path)
         .map_err(Error::ParsePath)?;
 
-    let site_root = site::SiteRoot::new(&state.config.server.sites_root, domain);
+    let site_root = site::SiteRoot::new(&state.storage, domain);
 
-    let site = site::get(&site_root)
+    let site = site::get_cached(&state.config.site, &site_root)
         .map_err(Error::GetSite)?;
 
-    let route = site.routes.get(&path)
+    let response = serve_route(req, state, &site_root, &site, &path)
+        .or_else(|err| serve_error_page(state, &site_root, &site, err))?;
+
+    Ok((response, site_headers(&site, &path)))
+}
+
+// Later headers override earlier ones: the site defaults, then the _headers file, then the route's own headers
+fn site_headers(site: &Site, path: &UrlPath) -> Vec<Header> {
+    let route_headers = match site.resolve_route(path) {
+        Some(ResolvedRoute::Route(route)) | Some(ResolvedRoute::NotFound(route)) =>
+            route.headers().to_vec(),
+
+        _ =>
+            vec![],
+    };
+
+    site.settings.headers
+        .iter()
+        .cloned()
+        .chain(site.rules.headers_for(&path.to_string()))
+        .chain(route_headers)
+        .collect()
+}
+
+fn serve_route(req: &SiteRequest, state: &AppState, site_root: &SiteRoot, site: &Site, path: &UrlPath) -> Result<Response, Error> {
+    let route = match site.resolve_route(path) {
+        Some(ResolvedRoute::Route(route)) =>
+            route,
+
+        Some(ResolvedRoute::Redirect(status, target)) =>
+            return Ok(Response::Redirect(status.status_code(), redirect_location(req, &target))),
+
+        Some(ResolvedRoute::NotFound(route)) =>
+            return serve_not_found_route(state, site_root, route),
+
+        None =>
+            return Err(Error::RouteNotFound()),
+    };
+
+    let file_info = match route {
+        RouteInfo::File{file_info, ..} =>
+            file_info,
+
+        RouteInfo::Redirect{redirect} =>
+            return Ok(Response::Redirect(redirect.status.status_code(), redirect_location(req, &redirect.target.to_string()))),
+    };
+
+    // Ranges are always served from the original file
+    let encoding = if compression::is_compressible(&file_info.mime) && !req.headers.contains_key(header::RANGE) {
+        accept_encoding::preferred(&req.headers)
+    } else {
+        None
+    };
+
+    if let Some(encoding) = encoding {
+        let validators = Validators::encoded(&file_info.hash, file_info.timestamp, encoding);
+
+        if cache::is_not_modified(&req.headers, &validators) {
+            return Ok(Response::NotModified(validators));
+        }
+
+        // Files that were stored before variants were introduced or that don't compress well have no variants
+        match site::open_route_file_variant(&state.config.site, site_root, file_info, encoding) {
+            Ok(file) =>
+                return Ok(Response::File(validators, Some(encoding), file)),
+
+            Err(err) if storage::is_not_found(&err) =>
+                (),
+
+            Err(err) =>
+                return Err(Error::ReadRouteData(err)),
+        }
+    }
+
+    let validators = Validators::new(&file_info.hash, file_info.timestamp);
+
+    // The file is not read when the client already has it
+    if cache::is_not_modified(&req.headers, &validators) {
+        return Ok(Response::NotModified(validators));
+    }
+
+    let size = file_info.size as u64;
+
+    let ranges = range::requested_ranges(&req.headers, &validators, size)
+        .map_err(|_| Error::RangeNotSatisfiable(size))?;
+
+    // Only the requested bytes are read from the blob
+    match ranges {
+        Some(mut ranges) if ranges.len() == 1 => {
+            let range = ranges.remove(0);
+
+            let object = site::open_route_file_range(site_root, file_info, range.start, range.length())
+                .map_err(Error::ReadRouteData)?;
+
+            Ok(Response::PartialFile(validators, file_info.clone(), range, object))
+        },
+
+        Some(ranges) => {
+            let parts = ranges
+                .into_iter()
+                .map(|range| {
+                    site::open_route_file_range(site_root, file_info, range.start, range.length())
+                        .map(|object| (range, object))
+                })
+                .collect::<Result<Vec<(ByteRange, ObjectReader)>, io::Error>>()
+                .map_err(Error::ReadRouteData)?;
+
+            let boundary = encryption_key::random_string(32);
+            let body = multipart_byteranges(&boundary, file_info, parts);
+
+            Ok(Response::MultipartFile(validators, boundary, body))
+        },
+
+        None => {
+            let file = site::open_route_file(&state.config.site, site_root, file_info)
+                .map_err(Error::ReadRouteData)?;
+
+            Ok(Response::File(validators, None, file))
+        },
+    }
+}
+
+fn serve_not_found_route(state: &AppState, site_root: &SiteRoot, route: &RouteInfo) -> Result<Response, Error> {
+    let file_info = route.file_info()
         .ok_or(Error::RouteNotFound())?;
 
-    site::read_route_file(&site_root, &route)
-        .map_err(Error::ReadRouteData)
+    let file = site::open_route_file(&state.config.site, site_root, file_info)
+        .map_err(Error::ReadRouteData)?;
+
+    Ok(Response::ErrorPage(StatusCode::NOT_FOUND, file))
 }
 
-fn prepare_response(file: site::File) -> HttpResponse {
-    http_helper::no_cache_headers(&mut HttpResponse::Ok())
-        .set_header(header::CONTENT_TYPE, file.metadata.mime)
-        .body(file.data)
+// The site's own page for the error is served if it has one, otherwise the error is returned as is
+fn serve_error_page(state: &AppState, site_root: &SiteRoot, site: &Site, err: Error) -> Result<Response, Error> {
+    let (status, page) = match &err {
+        Error::RouteNotFound() =>
+            (StatusCode::NOT_FOUND, &site.settings.not_found_page),
+
+        Error::ReadRouteData(read_err) if storage::is_not_found(read_err) =>
+            (StatusCode::NOT_FOUND, &site.settings.not_found_page),
+
+        Error::ReadRouteData(_) =>
+            (StatusCode::INTERNAL_SERVER_ERROR, &site.settings.error_page),
+
+        _ =>
+            return Err(err),
+    };
+
+    let file_info = match page.as_ref().and_then(|path| site.routes.get(path)).and_then(|route| route.file_info()) {
+        Some(file_info) =>
+            file_info,
+
+        None =>
+            return Err(err),
+    };
+
+    match site::open_route_file(&state.config.site, site_root, file_info) {
+        Ok(file) =>
+            Ok(Response::ErrorPage(status, file)),
+
+        Err(page_err) => {
+            log::error!("Failed to read error page: {}", page_err);
+            Err(err)
+        },
+    }
+}
+
+// The query string is kept so links with parameters still work after the redirect, unless the target has its own
+fn redirect_location(req: &SiteRequest, target: &str) -> String {
+    match req.query.as_deref() {
+        Some(query) if !target.contains('?') =>
+            format!("{}?{}", target, query),
+
+        _ =>
+            target.to_string(),
+    }
+}
+
+// The headers are validated when they are saved, they replace headers set by the server
+fn add_site_headers(response: &mut HttpResponse, site_headers: Vec<Header>) {
+    for site_header in site_headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_str(&site_header.name), HeaderValue::from_str(&site_header.value)) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+}
+
+fn prepare_response(cache_policy: &CachePolicy, response: Response) -> HttpResponse {
+    match response {
+        Response::Redirect(status, location) => {
+            HttpResponse::build(status)
+                .set_header(header::LOCATION, location)
+                .finish()
+        },
+
+        Response::ErrorPage(status, file) => {
+            http_helper::no_cache_headers(&mut HttpResponse::build(status))
+                .set_header(header::CONTENT_TYPE, file.metadata.mime)
+                .body(stream_body::sized(file.object.reader, file.object.size))
+        },
+
+        Response::NotModified(validators) => {
+            file_headers(&mut HttpResponse::NotModified(), cache_policy, &validators)
+                .finish()
+        },
+
+        Response::File(validators, Some(encoding), file) => {
+            file_headers(&mut HttpResponse::Ok(), cache_policy, &validators)
+                .set_header(header::CONTENT_ENCODING, encoding.content_encoding())
+                .set_header(header::CONTENT_TYPE, file.metadata.mime)
+                .body(stream_body::sized(file.object.reader, file.object.size))
+        },
+
+        Response::File(validators, None, file) => {
+            file_headers(&mut HttpResponse::Ok(), cache_policy, &validators)
+                .set_header(header::CONTENT_TYPE, file.metadata.mime)
+                .body(stream_body::sized(file.object.reader, file.object.size))
+        },
+
+        Response::PartialFile(validators, file_info, range, object) => {
+            file_headers(&mut HttpResponse::PartialContent(), cache_policy, &validators)
+                .set_header(header::CONTENT_RANGE, range.content_range(file_info.size as u64))
+                .set_header(header::CONTENT_TYPE, file_info.mime)
+                .body(stream_body::sized(object.reader, object.size))
+        },
+
+        Response::MultipartFile(validators, boundary, body) => {
+            file_headers(&mut HttpResponse::PartialContent(), cache_policy, &validators)
+                .set_header(header::CONTENT_TYPE, format!("multipart/byteranges; boundary={}", boundary))
+                .body(stream_body::sized(body.reader, body.size))
+        },
+    }
+}
+
+// The response depends on Accept-Encoding for compressible files, Vary is sent for all files for simplicity
+fn file_headers<'a>(builder: &'a mut HttpResponseBuilder, cache_policy: &CachePolicy, validators: &Validators) -> &'a mut HttpResponseBuilder {
+    cache::cache_headers(builder, cache_policy, validators)
+        .set_header(header::ACCEPT_RANGES, "bytes")
+        .set_header(header::VARY, "Accept-Encoding")
+}
+
+// The parts are chained into one reader so the body is streamed like a single file
+fn multipart_byteranges(boundary: &str, file_info: &FileInfo, parts: Vec<(ByteRange, ObjectReader)>) -> ObjectReader {
+    let mut reader: Box<dyn Read + Send> = Box::new(io::empty());
+    let mut size = 0;
+
+    for (range, object) in parts {
+        let part_headers = format!(
+            "\r\n--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+            boundary, file_info.mime, range.content_range(file_info.size as u64)
+        );
+
+        size += part_headers.len() as u64 + object.size;
+        reader = Box::new(reader.chain(io::Cursor::new(part_headers)).chain(object.reader));
+    }
+
+    let trailer = format!("\r\n--{}--\r\n", boundary);
+
+    size += trailer.len() as u64;
+    reader = Box::new(reader.chain(io::Cursor::new(trailer)));
+
+    ObjectReader{
+        reader,
+        size,
+    }
 }
 
 fn handle_error(err: Error) -> HttpResponse {
@@ -77,10 +383,26 @@ fn handle_error(err: Error) -> HttpResponse {
             HttpResponse::NotFound().finish()
         },
 
-        Error::ReadRouteData(err) => {
+        Error::ReadRouteData(err) if storage::is_not_found(&err) => {
             log::error!("Failed to read route data: {}", err);
             HttpResponse::NotFound().finish()
         },
+
+        Error::ReadRouteData(err) => {
+            log::error!("Failed to read route data: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
+
+        Error::RangeNotSatisfiable(size) => {
+            HttpResponse::RangeNotSatisfiable()
+                .set_header(header::CONTENT_RANGE, range::unsatisfied_content_range(size))
+                .finish()
+        },
+
+        Error::Canceled() => {
+            log::error!("The blocking work of the request was canceled");
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
 
@@ -95,5 +417,10 @@ fn handle_get_site_error(err: GetSiteError) -> HttpResponse {
             log::error!("Failed to read site json: {}", err);
             HttpResponse::InternalServerError().finish()
         },
+
+        GetSiteError::MigrateSiteJson(err) => {
+            log::error!("Failed to migrate site json: {}", err);
+            HttpResponse::InternalServerError().finish()
+        },
     }
 }
@@ -0,0 +1,327 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use rusqlite::{params, Connection, NO_PARAMS};
+use crate::orri::util;
+use crate::orri::domain::Domain;
+use crate::orri::storage::Storage;
+use crate::orri::site::{self, Site, GetSiteError};
+use crate::orri::site::revision::{self, ListRevisionsError};
+
+
+// Optional sqlite index of all sites. The site.json files are the source of truth,
+// the index is kept up to date when sites are persisted or removed and can be rebuilt with reindex
+#[derive(Clone)]
+pub struct SiteIndex {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl fmt::Debug for SiteIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SiteIndex")
+    }
+}
+
+
+pub struct IndexEntry {
+    pub domain: String,
+    pub quota: String,
+    pub route_count: usize,
+    pub size: usize,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+pub struct IndexStats {
+    pub site_count: usize,
+    pub total_size: usize,
+}
+
+
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    ListSites(std::io::Error),
+    GetSite(Domain, GetSiteError),
+    ListRevisions(Domain, ListRevisionsError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Sqlite(err) =>
+                write!(f, "Sqlite error: {}", err),
+
+            Error::ListSites(err) =>
+                write!(f, "Failed to list sites: {}", err),
+
+            Error::GetSite(domain, GetSiteError::SiteNotFound()) =>
+                write!(f, "Site not found: {}", domain),
+
+            Error::GetSite(domain, GetSiteError::ReadSiteJson(err)) =>
+                write!(f, "Failed to read site json for {}: {}", domain, err),
+
+            Error::GetSite(domain, GetSiteError::MigrateSiteJson(err)) =>
+                write!(f, "Failed to migrate site json for {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadDir(err)) =>
+                write!(f, "Failed to read revisions dir for {}: {}", domain, err),
+
+            Error::ListRevisions(domain, ListRevisionsError::ReadRevisionJson(err)) =>
+                write!(f, "Failed to read revision json for {}: {}", domain, err),
+        }
+    }
+}
+
+
+impl SiteIndex {
+    pub fn open(path: &Path) -> Result<SiteIndex, Error> {
+        let connection = Connection::open(path)
+            .map_err(Error::Sqlite)?;
+
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS sites (
+                domain TEXT PRIMARY KEY,
+                quota TEXT NOT NULL,
+                route_count INTEGER NOT NULL,
+                size INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            NO_PARAMS,
+        ).map_err(Error::Sqlite)?;
+
+        Ok(SiteIndex{
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection.lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    // The created_at timestamp is only set when the site is inserted
+    pub fn upsert(&self, entry: &IndexEntry) -> Result<(), Error> {
+        self.connection().execute(
+            "INSERT INTO sites (domain, quota, route_count, size, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT(domain) DO UPDATE SET
+                    quota = excluded.quota,
+                    route_count = excluded.route_count,
+                    size = excluded.size,
+                    updated_at = excluded.updated_at",
+            params![
+                entry.domain,
+                entry.quota,
+                entry.route_count as i64,
+                entry.size as i64,
+                entry.created_at as i64,
+                entry.updated_at as i64,
+            ],
+        ).map_err(Error::Sqlite)?;
+
+        Ok(())
+    }
+
+    pub fn remove(&self, domain: &Domain) -> Result<(), Error> {
+        self.connection().execute(
+            "DELETE FROM sites WHERE domain = ?1",
+            params![domain.to_string()],
+        ).map_err(Error::Sqlite)?;
+
+        Ok(())
+    }
+
+    // Lists sites ordered by domain, the query matches any part of the domain
+    pub fn list(&self, query: &str) -> Result<Vec<IndexEntry>, Error> {
+        let connection = self.connection();
+
+        let mut statement = connection.prepare(
+            "SELECT domain, quota, route_count, size, created_at, updated_at
+                FROM sites
+                WHERE domain LIKE ?1 ESCAPE '\\'
+                ORDER BY domain"
+        ).map_err(Error::Sqlite)?;
+
+        let pattern = format!("%{}%", escape_like(query));
+
+        let rows = statement.query_map(params![pattern], |row| {
+            Ok(IndexEntry{
+                domain: row.get(0)?,
+                quota: row.get(1)?,
+                route_count: row.get::<_, i64>(2)? as usize,
+                size: row.get::<_, i64>(3)? as usize,
+                created_at: row.get::<_, i64>(4)? as u64,
+                updated_at: row.get::<_, i64>(5)? as u64,
+            })
+        }).map_err(Error::Sqlite)?;
+
+        rows.collect::<Result<Vec<IndexEntry>, rusqlite::Error>>()
+            .map_err(Error::Sqlite)
+    }
+
+    pub fn stats(&self) -> Result<IndexStats, Error> {
+        self.connection().query_row(
+            "SELECT COUNT(*), COALESCE(SUM(size), 0) FROM sites",
+            NO_PARAMS,
+            |row| {
+                Ok(IndexStats{
+                    site_count: row.get::<_, i64>(0)? as usize,
+                    total_size: row.get::<_, i64>(1)? as usize,
+                })
+            },
+        ).map_err(Error::Sqlite)
+    }
+
+    // Rebuilds the index from the sites in the storage
+    pub fn reindex(&self, storage: &Storage) -> Result<IndexStats, Error> {
+        let mut entries = vec![];
+
+        for site_root in site::list_site_roots(storage).map_err(Error::ListSites)? {
+            let site = match site::get(&site_root) {
+                Ok(site) =>
+                    site,
+
+                Err(GetSiteError::SiteNotFound()) =>
+                    continue,
+
+                Err(err) =>
+                    return Err(Error::GetSite(site_root.domain.clone(), err)),
+            };
+
+            let revisions = revision::list(&site_root)
+                .map_err(|err| Error::ListRevisions(site_root.domain.clone(), err))?;
+
+            // Revisions are sorted newest first, the oldest stored revision is the best guess for when
+            // the site was created since older revisions may have been removed
+            let created_at = revisions.last()
+                .map(|revision| revision.timestamp)
+                .unwrap_or_else(|| oldest_file_timestamp(&site));
+
+            let updated_at = revisions.first()
+                .map(|revision| revision.timestamp)
+                .unwrap_or(created_at);
+
+            entries.push(entry_from_site(&site, created_at, updated_at));
+        }
+
+        let mut connection = self.connection();
+
+        let transaction = connection.transaction()
+            .map_err(Error::Sqlite)?;
+
+        transaction.execute("DELETE FROM sites", NO_PARAMS)
+            .map_err(Error::Sqlite)?;
+
+        for entry in &entries {
+            transaction.execute(
+                "INSERT INTO sites (domain, quota, route_count, size, created_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    entry.domain,
+                    entry.quota,
+                    entry.route_count as i64,
+                    entry.size as i64,
+                    entry.created_at as i64,
+                    entry.updated_at as i64,
+                ],
+            ).map_err(Error::Sqlite)?;
+        }
+
+        transaction.commit()
+            .map_err(Error::Sqlite)?;
+
+        drop(connection);
+
+        self.stats()
+    }
+}
+
+
+fn entry_from_site(site: &Site, created_at: u64, updated_at: u64) -> IndexEntry {
+    IndexEntry{
+        domain: site.domain.to_string(),
+        quota: site.quota.to_string(),
+        route_count: site.routes.len(),
+        size: site.size(),
+        created_at,
+        updated_at,
+    }
+}
+
+fn oldest_file_timestamp(site: &Site) -> u64 {
+    site.routes
+        .values()
+        .map(|route_info| route_info.timestamp())
+        .min()
+        .unwrap_or(0)
+}
+
+
+// Index errors are logged and not returned since the index can always be rebuilt from storage
+// % and _ in the query match themselves instead of acting as wildcards
+fn escape_like(query: &str) -> String {
+    query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub fn update_site(index: &Option<SiteIndex>, site: &Site) {
+    if let Some(index) = index {
+        let now = util::unix_timestamp(SystemTime::now());
+
+        if let Err(err) = index.upsert(&entry_from_site(site, now, now)) {
+            log::error!("Failed to update site index for {}: {}", site.domain, err);
+        }
+    }
+}
+
+pub fn remove_site(index: &Option<SiteIndex>, domain: &Domain) {
+    if let Some(index) = index {
+        if let Err(err) = index.remove(domain) {
+            log::error!("Failed to remove {} from site index: {}", domain, err);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(domain: &str) -> IndexEntry {
+        IndexEntry{
+            domain: domain.to_string(),
+            quota: "nano".to_string(),
+            route_count: 1,
+            size: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn list_domains(index: &SiteIndex, query: &str) -> Vec<String> {
+        index.list(query)
+            .unwrap_or_else(|err| panic!("{}", err))
+            .into_iter()
+            .map(|entry| entry.domain)
+            .collect()
+    }
+
+    #[test]
+    fn matches_wildcard_characters_literally() {
+        let index = SiteIndex::open(Path::new(":memory:"))
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        for domain in &["my_site.orri.dev", "myxsite.orri.dev", "other.orri.dev"] {
+            assert!(index.upsert(&entry(domain)).is_ok());
+        }
+
+        assert_eq!(list_domains(&index, "my_site"), vec!["my_site.orri.dev"]);
+        assert_eq!(list_domains(&index, "%"), Vec::<String>::new());
+        assert_eq!(list_domains(&index, "site"), vec!["my_site.orri.dev", "myxsite.orri.dev"]);
+        assert_eq!(list_domains(&index, ""), vec!["my_site.orri.dev", "myxsite.orri.dev", "other.orri.dev"]);
+    }
+}
@@ -0,0 +1,174 @@
+use std::io;
+use std::fs;
+use std::fmt;
+use std::sync::{Arc, Mutex, Condvar};
+use std::collections::HashSet;
+use fs2::FileExt;
+use crate::orri::domain::Domain;
+use crate::orri::site::SiteRoot;
+
+
+// Serializes writes to a site, both between requests in this process and between processes
+// sharing the same sites root. Taking the lock blocks, so handlers take it inside http::block
+// and it is never held across an await point.
+// There is no lock file on S3, deployments using it must run a single instance
+#[derive(Clone)]
+pub struct SiteLocks {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    locked: Mutex<HashSet<Domain>>,
+    released: Condvar,
+}
+
+impl fmt::Debug for SiteLocks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SiteLocks")
+    }
+}
+
+
+// Releases the lock on drop
+pub struct SiteLock {
+    inner: Arc<Inner>,
+    domain: Domain,
+    file: Option<fs::File>,
+}
+
+impl Drop for SiteLock {
+    fn drop(&mut self) {
+        if let Some(file) = &self.file {
+            let _ = file.unlock();
+        }
+
+        release(&self.inner, &self.domain);
+    }
+}
+
+
+impl SiteLocks {
+    pub fn new() -> SiteLocks {
+        SiteLocks{
+            inner: Arc::new(Inner{
+                locked: Mutex::new(HashSet::new()),
+                released: Condvar::new(),
+            }),
+        }
+    }
+
+    // Blocks until no one else holds the lock for the site
+    pub fn lock(&self, site_root: &SiteRoot) -> Result<SiteLock, io::Error> {
+        let domain = site_root.domain.clone();
+
+        let mut locked = self.inner.locked.lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        while locked.contains(&domain) {
+            locked = self.inner.released.wait(locked)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+
+        locked.insert(domain.clone());
+        drop(locked);
+
+        let file = open_lock_file(site_root)
+            .inspect_err(|_| release(&self.inner, &domain))?;
+
+        // The in-process lock is released by SiteLock's drop from here on
+        let lock = SiteLock{
+            inner: self.inner.clone(),
+            domain,
+            file,
+        };
+
+        if let Some(file) = &lock.file {
+            file.lock_exclusive()?;
+        }
+
+        Ok(lock)
+    }
+}
+
+fn release(inner: &Inner, domain: &Domain) {
+    let mut locked = inner.locked.lock()
+        .unwrap_or_else(|err| err.into_inner());
+
+    locked.remove(domain);
+    inner.released.notify_all();
+}
+
+// Storage that isn't on the local filesystem has no lock file, writes from other instances
+// are not serialized then. The optional revision check doesn't catch them either since the
+// site is read and written without a conditional request
+fn open_lock_file(site_root: &SiteRoot) -> Result<Option<fs::File>, io::Error> {
+    let path = match site_root.storage().local_path(&site_root.lock_key()) {
+        Some(path) =>
+            path,
+
+        None =>
+            return Ok(None),
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+
+    Ok(Some(file))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::orri::site::{Config, QuotaLimits};
+    use crate::orri::storage::Storage;
+    use crate::orri::storage::filesystem::FilesystemStorage;
+
+    fn config() -> Config {
+        Config{
+            quota_nano: QuotaLimits{
+                max_size: 0,
+                max_routes: 0,
+                max_sites: 0,
+            },
+            max_revisions: 0,
+            index: None,
+            cache: None,
+            blob_cache: None,
+        }
+    }
+
+    #[test]
+    fn removing_a_site_keeps_the_lock_file() {
+        let dir = tempfile::tempdir()
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        let storage: Storage = Arc::new(FilesystemStorage::new(dir.path()));
+        let domain = Domain::from_str("testsite.orri.dev")
+            .unwrap_or_else(|_| panic!("invalid domain"));
+
+        let site_root = SiteRoot::new(&storage, domain);
+        let site_locks = SiteLocks::new();
+
+        let lock = site_locks.lock(&site_root)
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        assert!(storage.write(&site_root.site_json_key(), b"{}").is_ok());
+        assert!(storage.write(&site_root.revision_key(1), b"{}").is_ok());
+        assert!(site_root.remove(&config()).is_ok());
+
+        assert!(!storage.exists(&site_root.site_json_key()).unwrap_or(true));
+        assert!(storage.list(&site_root.revisions_prefix()).unwrap_or_default().is_empty());
+        assert!(storage.exists(&site_root.lock_key()).unwrap_or(false));
+
+        drop(lock);
+    }
+}
@@ -1,35 +1,80 @@
 pub mod http;
+pub mod change_set;
+pub mod revision;
+pub mod lock;
+pub mod gc;
+pub mod index;
+pub mod schema;
+pub mod fsck;
+pub mod cache;
+pub mod settings;
+pub mod rules;
+pub mod headers;
 
 
 use serde::{Deserialize, Serialize};
 use std::io;
-use std::fs;
-use std::path::{PathBuf};
-use std::collections::BTreeMap;
+use std::fmt;
+use std::collections::{BTreeMap, HashSet};
 use crate::orri::file;
+use crate::orri::compression;
+use crate::orri::blob_store::BlobStore;
+use crate::orri::storage::{self, Storage, ObjectReader};
 use crate::orri::util;
 use crate::orri::domain::Domain;
 use crate::orri::site_key::SiteKey;
+use crate::orri::api_token::{self, ApiToken};
+use crate::orri::collaborator::{self, Collaborator};
 use crate::orri::url_path::UrlPath;
+use crate::orri::redirect;
 use std::time::SystemTime;
 use std::str::FromStr;
-use std::ffi::OsString;
+use std::sync::Arc;
 
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Site {
     pub domain: Domain,
     pub key: SiteKey,
+
+    // Scoped tokens for scripts, managed with the site key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<ApiToken>,
+
+    // Named keys with a role, the site key itself is the first owner
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub collaborators: Vec<Collaborator>,
+
     pub quota: Quota,
     pub routes: BTreeMap<UrlPath, RouteInfo>,
 
+    #[serde(default)]
+    pub revision: u64,
+
+    pub schema_version: u64,
+
+    #[serde(default)]
+    pub settings: settings::Settings,
+
+    // Parsed from the _redirects and _headers routes when the site is persisted
+    #[serde(default)]
+    pub rules: rules::Rules,
+
     #[serde(skip)]
     unwritten_files: Vec<File>,
+
+    // Files added or updated since the site was read, compressed variants are written for these when persisted
+    #[serde(skip)]
+    new_files: Vec<FileInfo>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Config {
     pub quota_nano: QuotaLimits,
+    pub max_revisions: u64,
+    pub index: Option<index::SiteIndex>,
+    pub cache: Option<cache::SiteCache>,
+    pub blob_cache: Option<cache::BlobCache>,
 }
 
 
@@ -43,126 +88,554 @@ pub enum UpdateRouteError {
     QuotaMaxSize(),
 }
 
+pub enum RemoveTokenError {
+    TokenNotFound(),
+}
+
+pub enum RemoveCollaboratorError {
+    CollaboratorNotFound(),
+}
+
+pub enum DeployError {
+    QuotaMaxSize(),
+    QuotaMaxRoutes(),
+}
+
 pub enum PersistSiteError {
     CreateDomainDir(io::Error),
-    WriteFileError(file::WriteError),
+    WriteFileError(io::Error),
     WriteSiteJsonError(file::WriteJsonError),
+    WriteRevisionJsonError(file::WriteJsonError),
+    ReadRulesFile(io::Error),
+    InvalidRules(Vec<rules::RuleError>),
 }
 
 
 impl Site {
     pub fn add_route(&mut self, config: &Config, path: UrlPath, file_info: FileInfo, file_data: &[u8]) -> Result<&Site, AddRouteError> {
+        self.add_uploaded_route(config, path, file_info.clone())?;
+
+        self.unwritten_files.push(File{
+            metadata: file_info,
+            data: file_data.to_vec(),
+        });
+
+        Ok(self)
+    }
+
+    // Adds a route for a file that is written to the data dir by the caller, i.e. a streamed upload
+    pub fn add_uploaded_route(&mut self, config: &Config, path: UrlPath, file_info: FileInfo) -> Result<&Site, AddRouteError> {
         let limits = self.quota.limits(config);
 
         util::ensure(self.size() + file_info.size < limits.max_size, AddRouteError::QuotaMaxSize())?;
         util::ensure(self.routes.len() < limits.max_routes, AddRouteError::QuotaMaxRoutes())?;
 
-        self.unwritten_files.push(File{
-            metadata: file_info.clone(),
-            data: file_data.to_vec(),
-        });
+        self.new_files.push(file_info.clone());
 
-        self.routes.insert(path, RouteInfo{
+        self.routes.insert(path, RouteInfo::File{
             file_info,
+            headers: vec![],
         });
 
         Ok(self)
     }
 
     pub fn update_route(&mut self, config: &Config, path: UrlPath, file_info: FileInfo, file_data: &[u8]) -> Result<&Site, UpdateRouteError> {
+        self.update_uploaded_route(config, path, file_info.clone())?;
+
+        self.unwritten_files.push(File{
+            metadata: file_info,
+            data: file_data.to_vec(),
+        });
+
+        Ok(self)
+    }
+
+    // Updates a route with a file that is written to the data dir by the caller, i.e. a streamed upload
+    pub fn update_uploaded_route(&mut self, config: &Config, path: UrlPath, file_info: FileInfo) -> Result<&Site, UpdateRouteError> {
         let limits = self.quota.limits(config);
 
         let old_route = self.routes.get(&path)
             .ok_or(UpdateRouteError::RouteNotFound())?;
 
-        util::ensure(self.size() - old_route.file_info.size + file_info.size < limits.max_size, UpdateRouteError::QuotaMaxSize())?;
+        util::ensure(self.size() - old_route.size() + file_info.size < limits.max_size, UpdateRouteError::QuotaMaxSize())?;
 
-        self.unwritten_files.push(File{
-            metadata: file_info.clone(),
-            data: file_data.to_vec(),
-        });
+        // The headers of the route are kept when the file is replaced
+        let headers = old_route.headers().to_vec();
+
+        self.new_files.push(file_info.clone());
 
-        self.routes.insert(path, RouteInfo{
+        self.routes.insert(path, RouteInfo::File{
             file_info,
+            headers,
         });
 
         Ok(self)
     }
 
+    // Replaces all routes with routes for files that are written to the data dir by the caller.
+    // The current root route is kept unless it's replaced, quota limits are checked against the final routes
+    // Redirect routes that are not replaced by a file are kept, their paths are returned
+    pub fn deploy_uploaded_routes(&mut self, config: &Config, routes: Vec<(UrlPath, FileInfo)>) -> Result<Vec<UrlPath>, DeployError> {
+        let limits = self.quota.limits(config);
+
+        let new_files = routes
+            .iter()
+            .map(|(_path, file_info)| file_info.clone())
+            .collect::<Vec<FileInfo>>();
+
+        // Paths that are deployed again keep their headers
+        let mut new_routes = routes
+            .into_iter()
+            .map(|(path, file_info)| {
+                let headers = self.routes.get(&path)
+                    .map(|route| route.headers().to_vec())
+                    .unwrap_or_default();
+
+                (path, RouteInfo::File{ file_info, headers })
+            })
+            .collect::<BTreeMap<UrlPath, RouteInfo>>();
+
+        if let Some(root_route) = self.routes.get(&UrlPath::root()) {
+            new_routes.entry(UrlPath::root())
+                .or_insert_with(|| root_route.clone());
+        }
+
+        let kept_redirects = self.routes
+            .iter()
+            .filter(|(path, route)| route.is_redirect() && !new_routes.contains_key(path))
+            .map(|(path, route)| (path.clone(), route.clone()))
+            .collect::<Vec<(UrlPath, RouteInfo)>>();
+
+        let kept_redirect_paths = kept_redirects
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        new_routes.extend(kept_redirects);
+
+        util::ensure(routes_size(&new_routes) < limits.max_size, DeployError::QuotaMaxSize())?;
+        util::ensure(new_routes.len() <= limits.max_routes, DeployError::QuotaMaxRoutes())?;
+
+        self.routes = new_routes;
+        self.new_files.extend(new_files);
+
+        Ok(kept_redirect_paths)
+    }
+
+    pub fn add_redirect_route(&mut self, config: &Config, path: UrlPath, redirect: RedirectInfo) -> Result<&Site, AddRouteError> {
+        let limits = self.quota.limits(config);
+
+        util::ensure(self.routes.len() < limits.max_routes, AddRouteError::QuotaMaxRoutes())?;
+
+        self.routes.insert(path, RouteInfo::Redirect{
+            redirect,
+        });
+
+        Ok(self)
+    }
+
+    // The existing route can be a file or a redirect
+    pub fn update_redirect_route(&mut self, path: UrlPath, redirect: RedirectInfo) -> Result<&Site, UpdateRouteError> {
+        util::ensure(self.routes.contains_key(&path), UpdateRouteError::RouteNotFound())?;
+
+        self.routes.insert(path, RouteInfo::Redirect{
+            redirect,
+        });
+
+        Ok(self)
+    }
+
+    // Only file routes have headers, a redirect has nothing to add them to
+    pub fn update_route_headers(&mut self, path: &UrlPath, new_headers: Vec<headers::Header>) -> Result<&Site, UpdateRouteError> {
+        match self.routes.get_mut(path) {
+            Some(RouteInfo::File{headers, ..}) => {
+                *headers = new_headers;
+                Ok(self)
+            },
+
+            _ =>
+                Err(UpdateRouteError::RouteNotFound()),
+        }
+    }
+
     pub fn remove_route(&mut self, path: UrlPath) -> &Site {
         self.routes.remove(&path);
 
         self
     }
 
+    pub fn add_token(&mut self, token: ApiToken, time: SystemTime) -> Result<&Site, api_token::Error> {
+        // Expired tokens are removed so they don't count toward the limit
+        self.tokens.retain(|token| !token.is_expired(time));
+
+        util::ensure(self.tokens.len() < api_token::MAX_TOKENS, api_token::Error::TooMany())?;
+
+        self.tokens.push(token);
+
+        Ok(self)
+    }
+
+    pub fn remove_token(&mut self, id: &str) -> Result<&Site, RemoveTokenError> {
+        util::ensure(self.tokens.iter().any(|token| token.id == id), RemoveTokenError::TokenNotFound())?;
+
+        self.tokens.retain(|token| token.id != id);
+
+        Ok(self)
+    }
+
+    // Tokens were handed out under the old key and are removed with it, collaborators keep their own keys
+    pub fn rotate_key(&mut self, key: SiteKey) -> &Site {
+        self.key = key;
+        self.tokens.clear();
+
+        self
+    }
+
+    pub fn add_collaborator(&mut self, collaborator: Collaborator) -> Result<&Site, collaborator::Error> {
+        util::ensure(self.collaborators.len() < collaborator::MAX_COLLABORATORS, collaborator::Error::TooMany())?;
+
+        self.collaborators.push(collaborator);
+
+        Ok(self)
+    }
+
+    // Sessions of the collaborator lose their access since the capability refers to the removed id
+    pub fn remove_collaborator(&mut self, id: &str) -> Result<&Site, RemoveCollaboratorError> {
+        util::ensure(collaborator::find(&self.collaborators, id).is_some(), RemoveCollaboratorError::CollaboratorNotFound())?;
+
+        self.collaborators.retain(|collaborator| collaborator.id != id);
+
+        Ok(self)
+    }
+
     pub fn size(&self) -> usize {
-        self.routes
-            .iter()
-            .fold(0, |acc, (_path, route_info)| acc + route_info.file_info.size)
+        routes_size(&self.routes)
+    }
+
+    // Rules from the _redirects file apply before the fallback route, and before the
+    // route itself when forced. Paths without a route are served from the fallback route unless excluded
+    pub fn resolve_route(&self, path: &UrlPath) -> Option<ResolvedRoute<'_>> {
+        let path_str = path.to_string();
+
+        if rules::is_rules_path(&path_str) {
+            return None;
+        }
+
+        let route = self.resolve_directory_route(path);
+
+        match self.rules.find_redirect(&path_str, route.is_some()) {
+            Some(found) =>
+                self.resolve_redirect_rule(found),
+
+            None =>
+                route.or_else(|| self.fallback_route(path).map(ResolvedRoute::Route)),
+        }
+    }
+
+    fn resolve_redirect_rule(&self, found: rules::RedirectMatch<'_>) -> Option<ResolvedRoute<'_>> {
+        match found.rule.status {
+            rules::RuleStatus::Redirect(status) =>
+                Some(ResolvedRoute::Redirect(status, found.target)),
+
+            rules::RuleStatus::Rewrite() =>
+                self.rewrite_route(&found.target).map(ResolvedRoute::Route),
+
+            rules::RuleStatus::NotFound() =>
+                self.rewrite_route(&found.target).map(ResolvedRoute::NotFound),
+        }
+    }
+
+    // The target of a rewrite is served without redirecting, a directory is served from its index
+    fn rewrite_route(&self, target: &str) -> Option<&RouteInfo> {
+        let path = UrlPath::from_str(target).ok()?;
+
+        self.routes.get(&path)
+            .or_else(|| self.routes.get(&path.index_path()))
     }
 
-    pub fn persist(&self, site_root: &SiteRoot) -> Result<&Site, PersistSiteError> {
+    // An exact match always wins. Otherwise /docs and /docs/ are served from /docs/index.html,
+    // or from the other form of the path, and redirected if the other form is the canonical one
+    fn resolve_directory_route(&self, path: &UrlPath) -> Option<ResolvedRoute<'_>> {
+        if let Some(route) = self.routes.get(path) {
+            return Some(ResolvedRoute::Route(route));
+        }
+
+        if *path == UrlPath::root() {
+            return self.routes.get(&path.index_path())
+                .map(ResolvedRoute::Route);
+        }
+
+        let (other_path, redirect) = if path.has_trailing_slash() {
+            (path.without_trailing_slash(), self.settings.trailing_slash == settings::TrailingSlash::Remove)
+        } else {
+            (path.with_trailing_slash(), self.settings.trailing_slash == settings::TrailingSlash::Add)
+        };
+
+        let route = self.routes.get(&path.index_path())
+            .or_else(|| self.routes.get(&other_path))?;
+
+        if redirect {
+            Some(ResolvedRoute::Redirect(redirect::Status::MovedPermanently(), other_path.to_string()))
+        } else {
+            Some(ResolvedRoute::Route(route))
+        }
+    }
+
+    fn fallback_route(&self, path: &UrlPath) -> Option<&RouteInfo> {
+        if self.settings.fallback_exclude.matches(path) {
+            return None;
+        }
+
+        self.settings.fallback_route
+            .as_ref()
+            .and_then(|fallback_path| self.routes.get(fallback_path))
+    }
+
+    pub fn update_settings(&mut self, settings: settings::Settings) -> &Site {
+        self.settings = settings;
+
+        self
+    }
+
+    // Used for optimistic concurrency, a client that provides the revision it last saw
+    // gets a conflict if the site has been changed since
+    pub fn is_expected_revision(&self, expected_revision: Option<u64>) -> bool {
+        expected_revision
+            .map(|revision| revision == self.revision)
+            .unwrap_or(true)
+    }
+
+    pub fn persist(&mut self, config: &Config, site_root: &SiteRoot) -> Result<&Site, PersistSiteError> {
+        // Invalid rules are rejected before anything is written
+        self.rules = self.parse_rules(site_root)?;
+
         site_root.prepare_directories()
             .map_err(PersistSiteError::CreateDomainDir)?;
 
         self.unwritten_files
             .iter()
             .try_for_each(|file|
-                file::write(&site_root.data_file_path(&file.metadata.hash), &file.data)
+                site_root.blob_store().write(&file.metadata.hash, &file.data)
             )
             .map_err(PersistSiteError::WriteFileError)?;
 
-        file::write_json(&site_root.site_json_path(), self)
+        self.write_compressed_variants(site_root);
+
+        self.revision += 1;
+        self.schema_version = schema::CURRENT_VERSION;
+
+        let timestamp = util::unix_timestamp(SystemTime::now());
+
+        storage::write_json(site_root.storage(), &site_root.revision_key(self.revision), &self.current_revision(timestamp))
+            .map_err(PersistSiteError::WriteRevisionJsonError)?;
+
+        storage::write_json(site_root.storage(), &site_root.site_json_key(), self)
             .map_err(PersistSiteError::WriteSiteJsonError)?;
 
-        let _ = self.remove_stale_data(site_root);
+        self.unwritten_files.clear();
+        self.new_files.clear();
+
+        index::update_site(&config.index, self);
+        invalidate_cache(config, &self.domain);
+
+        let _ = revision::remove_expired(site_root, self.revision, config.max_revisions);
 
         Ok(self)
     }
 
-    fn remove_stale_data(&self, site_root: &SiteRoot) -> Result<(), io::Error> {
-        let fresh_hashes = self.routes.iter()
-            .map(|(_path, route_info)| OsString::from(route_info.file_info.hash.clone()))
-            .collect::<Vec<OsString>>();
+    fn parse_rules(&self, site_root: &SiteRoot) -> Result<rules::Rules, PersistSiteError> {
+        let redirects = self.read_rules_file(site_root, rules::REDIRECTS_PATH)?;
+        let headers = self.read_rules_file(site_root, rules::HEADERS_PATH)?;
 
-        fs::read_dir(site_root.data_path())?
-            .for_each(|res| {
-                let _ = res.map(|entry| {
-                    if !fresh_hashes.contains(&entry.file_name()) {
-                        let _ = fs::remove_file(entry.path());
-                    }
-                });
-            });
+        rules::parse(redirects.as_deref(), headers.as_deref())
+            .map_err(PersistSiteError::InvalidRules)
+    }
 
-        Ok(())
+    // The file is read from the unwritten files if it was just added, otherwise from storage
+    fn read_rules_file(&self, site_root: &SiteRoot, file: &'static str) -> Result<Option<String>, PersistSiteError> {
+        let file_info = match UrlPath::from_str(file).ok().and_then(|path| self.routes.get(&path)).and_then(|route| route.file_info()) {
+            Some(file_info) =>
+                file_info,
+
+            None =>
+                return Ok(None),
+        };
+
+        let invalid = |message: String| PersistSiteError::InvalidRules(vec![rules::file_error(file, message)]);
+
+        util::ensure(file_info.size <= rules::MAX_FILE_SIZE, invalid(format!("The file must not be larger than {} bytes", rules::MAX_FILE_SIZE)))?;
+
+        let unwritten_file = self.unwritten_files
+            .iter()
+            .find(|unwritten| unwritten.metadata.hash == file_info.hash);
+
+        let data = match unwritten_file {
+            Some(unwritten) =>
+                unwritten.data.clone(),
+
+            None =>
+                site_root.read_data_file(&file_info.hash)
+                    .map_err(PersistSiteError::ReadRulesFile)?,
+        };
+
+        String::from_utf8(data)
+            .map(Some)
+            .map_err(|_| invalid("The file must be valid UTF-8".to_string()))
+    }
+
+    // Variants are optional, a failure is logged and the file is served as is.
+    // They are not part of the site size, only file_info.size counts toward the quota
+    fn write_compressed_variants(&self, site_root: &SiteRoot) {
+        let blob_store = site_root.blob_store();
+        let mut written = HashSet::new();
+
+        for file_info in &self.new_files {
+            if !compression::is_compressible(&file_info.mime) || !written.insert(file_info.hash.as_str()) {
+                continue;
+            }
+
+            let unwritten_file = self.unwritten_files
+                .iter()
+                .find(|file| file.metadata.hash == file_info.hash);
+
+            let result = match unwritten_file {
+                Some(file) =>
+                    blob_store.write_variants(&file_info.hash, &file.data),
+
+                None =>
+                    blob_store.read(&file_info.hash)
+                        .and_then(|data| blob_store.write_variants(&file_info.hash, &data)),
+            };
+
+            if let Err(err) = result {
+                log::warn!("Failed to write compressed variants of {}: {}", file_info.hash, err);
+            }
+        }
     }
 }
 
+// Untagged so routes stored before redirects existed, which only have file_info, are read as files
 #[derive(Deserialize, Serialize, Clone)]
-pub struct RouteInfo {
-    pub file_info: FileInfo,
+#[serde(untagged)]
+pub enum RouteInfo {
+    File{
+        file_info: FileInfo,
+
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        headers: Vec<headers::Header>,
+    },
+
+    Redirect{
+        redirect: RedirectInfo,
+    },
+}
+
+impl RouteInfo {
+    pub fn file_info(&self) -> Option<&FileInfo> {
+        match self {
+            RouteInfo::File{file_info, ..} =>
+                Some(file_info),
+
+            RouteInfo::Redirect{..} =>
+                None,
+        }
+    }
+
+    pub fn headers(&self) -> &[headers::Header] {
+        match self {
+            RouteInfo::File{headers, ..} =>
+                headers,
+
+            RouteInfo::Redirect{..} =>
+                &[],
+        }
+    }
+
+    pub fn is_redirect(&self) -> bool {
+        matches!(self, RouteInfo::Redirect{..})
+    }
+
+    // Redirects don't count toward the quota size
+    pub fn size(&self) -> usize {
+        self.file_info()
+            .map(|file_info| file_info.size)
+            .unwrap_or(0)
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            RouteInfo::File{file_info, ..} =>
+                file_info.timestamp,
+
+            RouteInfo::Redirect{redirect} =>
+                redirect.timestamp,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct RedirectInfo {
+    pub target: redirect::Target,
+    pub status: redirect::Status,
+    pub timestamp: u64,
+}
+
+impl RedirectInfo {
+    pub fn new(target: redirect::Target, status: redirect::Status, time: SystemTime) -> RedirectInfo {
+        RedirectInfo{
+            target,
+            status,
+            timestamp: util::unix_timestamp(time),
+        }
+    }
+}
+
+pub enum ResolvedRoute<'a> {
+    Route(&'a RouteInfo),
+    Redirect(redirect::Status, String),
+
+    // Served with status 404 by a rule in the _redirects file
+    NotFound(&'a RouteInfo),
+}
+
+fn routes_size(routes: &BTreeMap<UrlPath, RouteInfo>) -> usize {
+    routes
+        .iter()
+        .fold(0, |acc, (_path, route_info)| acc + route_info.size())
 }
 
 
 
 pub enum CreateSiteError {
+    CheckSiteExist(io::Error),
     SiteAlreadyExist(),
     AddRoute(AddRouteError),
 }
 
 
 pub fn create(config: &Config, site_root: &SiteRoot, key: SiteKey, file_info: FileInfo, file_data: &[u8]) -> Result<Site, CreateSiteError> {
-    util::ensure(!site_root.site_json_path().exists(), CreateSiteError::SiteAlreadyExist())?;
+    let site_exist = site_root.site_exist()
+        .map_err(CreateSiteError::CheckSiteExist)?;
+
+    util::ensure(!site_exist, CreateSiteError::SiteAlreadyExist())?;
 
     let mut site = Site{
         domain: site_root.domain.clone(),
         key,
+        tokens: vec![],
+        collaborators: vec![],
         quota: Quota::Nano,
         routes: BTreeMap::new(),
+        revision: 0,
+        schema_version: schema::CURRENT_VERSION,
+        settings: settings::Settings::default(),
+        rules: rules::Rules::default(),
         unwritten_files: vec![],
+        new_files: vec![],
     };
 
-    site.add_route(&config, UrlPath::root(), file_info, file_data)
+    site.add_route(config, UrlPath::root(), file_info, file_data)
         .map_err(CreateSiteError::AddRoute)?;
 
     Ok(site)
@@ -172,51 +645,243 @@ pub fn create(config: &Config, site_root: &SiteRoot, key: SiteKey, file_info: Fi
 pub enum GetSiteError {
     SiteNotFound(),
     ReadSiteJson(file::ReadJsonError),
+    MigrateSiteJson(schema::Error),
 }
 
+// Documents with an older schema version are upgraded in memory,
+// they are written with the current version the next time the site is persisted
 pub fn get(site_root: &SiteRoot) -> Result<Site, GetSiteError> {
-    util::ensure(site_root.site_json_path().exists(), GetSiteError::SiteNotFound())?;
+    let migrated = read_migrated(site_root)?;
 
-    file::read_json(&site_root.site_json_path())
-        .map_err(GetSiteError::ReadSiteJson)
+    serde_json::from_value(migrated.document)
+        .map_err(|err| GetSiteError::ReadSiteJson(file::ReadJsonError::Deserialize(err)))
+}
+
+fn read_migrated(site_root: &SiteRoot) -> Result<schema::Migrated, GetSiteError> {
+    let document = storage::read_json(site_root.storage(), &site_root.site_json_key())
+        .map_err(|err| match err {
+            file::ReadJsonError::Open(err) if storage::is_not_found(&err) =>
+                GetSiteError::SiteNotFound(),
+
+            err =>
+                GetSiteError::ReadSiteJson(err),
+        })?;
+
+    schema::migrate(document)
+        .map_err(GetSiteError::MigrateSiteJson)
+}
+
+
+pub enum MigrateSiteError {
+    GetSite(GetSiteError),
+    WriteSiteJson(file::WriteJsonError),
+    MoveLegacyData(io::Error),
+}
+
+pub enum MigrateResult {
+    Migrated(u64),
+    UpToDate(),
+    SiteNotFound(),
+}
+
+// Rewrites site.json with the current schema version and moves the files of the legacy data dir
+// into the blob store, the revision is left as is since the content of the site is unchanged
+pub fn migrate(site_root: &SiteRoot) -> Result<MigrateResult, MigrateSiteError> {
+    let migrated = match read_migrated(site_root) {
+        Ok(migrated) =>
+            migrated,
+
+        Err(GetSiteError::SiteNotFound()) =>
+            return Ok(MigrateResult::SiteNotFound()),
+
+        Err(err) =>
+            return Err(MigrateSiteError::GetSite(err)),
+    };
+
+    // Files are read from the legacy data dir until it has been moved
+    site_root.move_legacy_data_to_blob_store()
+        .map_err(MigrateSiteError::MoveLegacyData)?;
+
+    if !migrated.is_changed() {
+        return Ok(MigrateResult::UpToDate());
+    }
+
+    let site: Site = serde_json::from_value(migrated.document)
+        .map_err(|err| MigrateSiteError::GetSite(GetSiteError::ReadSiteJson(file::ReadJsonError::Deserialize(err))))?;
+
+    storage::write_json(site_root.storage(), &site_root.site_json_key(), &site)
+        .map_err(MigrateSiteError::WriteSiteJson)?;
+
+    Ok(MigrateResult::Migrated(migrated.from_version))
+}
+
+// Used when serving pages, changes to the site must go through get since the cached site may be stale
+pub fn get_cached(config: &Config, site_root: &SiteRoot) -> Result<Arc<Site>, GetSiteError> {
+    match &config.cache {
+        Some(cache) =>
+            cache.get_or_insert_with(&site_root.domain, || get(site_root)),
+
+        None =>
+            get(site_root).map(Arc::new),
+    }
+}
+
+fn invalidate_cache(config: &Config, domain: &Domain) {
+    if let Some(cache) = &config.cache {
+        cache.invalidate(domain);
+    }
+}
+
+// All top level dirs in the storage that are named like a domain
+pub fn list_site_roots(storage: &Storage) -> Result<Vec<SiteRoot>, io::Error> {
+    let site_roots = storage.list_dirs("")?
+        .iter()
+        .filter_map(|name| Domain::from_str(name).ok())
+        .map(|domain| SiteRoot::new(storage, domain))
+        .collect();
+
+    Ok(site_roots)
 }
 
 
 
 pub struct SiteRoot {
     domain: Domain,
-    root: PathBuf,
+    storage: Storage,
+    blob_store: BlobStore,
 }
 
 impl SiteRoot {
-    pub fn new(root_path: &PathBuf, domain: Domain) -> SiteRoot {
-        let root = root_path.join(PathBuf::from(domain.to_string()));
-
+    pub fn new(storage: &Storage, domain: Domain) -> SiteRoot {
         SiteRoot{
             domain,
-            root,
+            storage: storage.clone(),
+            blob_store: BlobStore::new(storage),
         }
     }
 
-    pub fn site_json_path(&self) -> PathBuf {
-        self.root.join(PathBuf::from("site.json"))
+    pub fn domain(&self) -> &Domain {
+        &self.domain
     }
 
-    pub fn data_path(&self) -> PathBuf {
-        self.root.join(PathBuf::from("data"))
+    pub fn storage(&self) -> &Storage {
+        &self.storage
     }
 
+    pub fn blob_store(&self) -> &BlobStore {
+        &self.blob_store
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("{}/{}", self.domain, name)
+    }
+
+    pub fn lock_key(&self) -> String {
+        self.key(".lock")
+    }
 
-    pub fn data_file_path(&self, data_hash: &str) -> PathBuf {
-        self.data_path().join(PathBuf::from(data_hash))
+    pub fn site_json_key(&self) -> String {
+        self.key("site.json")
+    }
+
+    pub fn revisions_prefix(&self) -> String {
+        self.key("revisions/")
+    }
+
+    pub fn revision_key(&self, revision: u64) -> String {
+        self.key(&format!("revisions/{}.json", revision))
+    }
+
+    // Sites used to have their own data dir before the shared blob store was introduced
+    fn legacy_data_prefix(&self) -> String {
+        self.key("data/")
+    }
+
+    fn legacy_data_key(&self, data_hash: &str) -> String {
+        format!("{}{}", self.legacy_data_prefix(), data_hash)
+    }
+
+    // Unreadable site.json files are moved here by fsck
+    pub fn quarantine_prefix(&self) -> String {
+        self.key("quarantine/")
+    }
+
+    pub fn quarantine_key(&self, timestamp: u64) -> String {
+        self.key(&format!("quarantine/site.{}.json", timestamp))
+    }
+
+    // A quarantined site still exists, this keeps the domain from being claimed by someone else
+    pub fn site_exist(&self) -> Result<bool, io::Error> {
+        if self.storage.exists(&self.site_json_key())? {
+            return Ok(true);
+        }
+
+        let quarantined = self.storage.list(&self.quarantine_prefix())?;
+
+        Ok(!quarantined.is_empty())
+    }
+
+    pub fn read_data_file(&self, data_hash: &str) -> Result<Vec<u8>, io::Error> {
+        match self.blob_store.read(data_hash) {
+            Err(err) if storage::is_not_found(&err) =>
+                self.storage.read(&self.legacy_data_key(data_hash)),
+
+            res =>
+                res,
+        }
+    }
+
+    pub fn open_data_file(&self, data_hash: &str) -> Result<ObjectReader, io::Error> {
+        match self.blob_store.open(data_hash) {
+            Err(err) if storage::is_not_found(&err) =>
+                self.storage.open(&self.legacy_data_key(data_hash)),
+
+            res =>
+                res,
+        }
+    }
+
+    pub fn open_data_file_range(&self, data_hash: &str, offset: u64, length: u64) -> Result<ObjectReader, io::Error> {
+        match self.blob_store.open_range(data_hash, offset, length) {
+            Err(err) if storage::is_not_found(&err) =>
+                self.storage.open_range(&self.legacy_data_key(data_hash), offset, length),
+
+            res =>
+                res,
+        }
     }
 
     pub fn prepare_directories(&self) -> Result<(), io::Error> {
-        fs::create_dir_all(self.data_path())
+        self.blob_store.prepare_directories()
     }
 
-    pub fn remove(&self) -> Result<(), io::Error> {
-        fs::remove_dir_all(&self.root)?;
+    // Moves the files in the legacy data dir into the blob store and removes the dir
+    pub fn move_legacy_data_to_blob_store(&self) -> Result<(), io::Error> {
+        let prefix = self.legacy_data_prefix();
+
+        for object in self.storage.list(&prefix)? {
+            let hash = object.key.trim_start_matches(&prefix);
+            let data = self.storage.read(&object.key)?;
+
+            self.blob_store.write(hash, &data)?;
+        }
+
+        self.storage.remove_all(&prefix)
+    }
+
+    // The lock file is kept since the caller holds the lock. Removing it would let the next
+    // writer create and lock a new file while a waiting writer gets the lock on the removed one
+    pub fn remove(&self, config: &Config) -> Result<(), io::Error> {
+        for object in self.storage.list(&self.key(""))? {
+            self.storage.remove(&object.key)?;
+        }
+
+        for dir in self.storage.list_dirs(&self.key(""))? {
+            self.storage.remove_all(&self.key(&format!("{}/", dir)))?;
+        }
+
+        index::remove_site(&config.index, &self.domain);
+        invalidate_cache(config, &self.domain);
 
         Ok(())
     }
@@ -233,7 +898,7 @@ pub struct FileInfo {
 
 impl FileInfo {
     pub fn new(data: &[u8], mime: String, time: SystemTime) -> FileInfo {
-        let hash = util::sha256(&data);
+        let hash = util::sha256(data);
         let timestamp = util::unix_timestamp(time);
 
         FileInfo{
@@ -243,6 +908,17 @@ impl FileInfo {
             timestamp,
         }
     }
+
+    pub fn from_hashed_file(hashed_file: &file::HashedFile, mime: String, time: SystemTime) -> FileInfo {
+        let timestamp = util::unix_timestamp(time);
+
+        FileInfo{
+            mime,
+            hash: hashed_file.hash.clone(),
+            size: hashed_file.size,
+            timestamp,
+        }
+    }
 }
 
 
@@ -252,15 +928,50 @@ pub struct File {
     pub data: Vec<u8>,
 }
 
-pub fn read_route_file(site_root: &SiteRoot, route: &RouteInfo) -> Result<File, io::Error> {
-    let path = site_root.data_file_path(&route.file_info.hash);
-    let data = fs::read(path)?;
+// A route file that is streamed from the storage instead of being read into memory
+pub struct FileStream {
+    pub metadata: FileInfo,
+    pub object: ObjectReader,
+}
+
+pub fn open_route_file(config: &Config, site_root: &SiteRoot, file_info: &FileInfo) -> Result<FileStream, io::Error> {
+    let hash = &file_info.hash;
+    let key = site_root.blob_store().blob_key(hash);
 
-    Ok(File{
-        metadata: route.file_info.clone(),
-        data,
+    let object = open_cached(config, &key, || site_root.open_data_file(hash))?;
+
+    Ok(FileStream{
+        metadata: file_info.clone(),
+        object,
     })
+}
+
+pub fn open_route_file_variant(config: &Config, site_root: &SiteRoot, file_info: &FileInfo, encoding: compression::Encoding) -> Result<FileStream, io::Error> {
+    let hash = &file_info.hash;
+    let key = site_root.blob_store().variant_key(hash, encoding);
+
+    let object = open_cached(config, &key, || site_root.blob_store().open_variant(hash, encoding))?;
+
+    Ok(FileStream{
+        metadata: file_info.clone(),
+        object,
+    })
+}
+
+fn open_cached<F>(config: &Config, key: &str, open_object: F) -> Result<ObjectReader, io::Error>
+    where F: FnOnce() -> Result<ObjectReader, io::Error> {
+
+    match &config.blob_cache {
+        Some(blob_cache) =>
+            blob_cache.open(key, open_object),
 
+        None =>
+            open_object(),
+    }
+}
+
+pub fn open_route_file_range(site_root: &SiteRoot, file_info: &FileInfo, offset: u64, length: u64) -> Result<ObjectReader, io::Error> {
+    site_root.open_data_file_range(&file_info.hash, offset, length)
 }
 
 
@@ -270,6 +981,15 @@ pub enum Quota {
     Nano,
 }
 
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Quota::Nano =>
+                write!(f, "nano"),
+        }
+    }
+}
+
 impl Quota {
     pub fn limits(&self, config: &Config) -> QuotaLimits {
         match self {
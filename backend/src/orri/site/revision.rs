@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::cmp;
+use std::io;
+use std::path::PathBuf;
+use crate::orri::file;
+use crate::orri::storage;
+use crate::orri::util;
+use crate::orri::url_path::UrlPath;
+use crate::orri::site::{self, Site, SiteRoot, Config, RouteInfo};
+
+
+// A snapshot of the route table, written every time a site is persisted
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Revision {
+    pub revision: u64,
+    pub timestamp: u64,
+    pub routes: BTreeMap<UrlPath, RouteInfo>,
+}
+
+impl Revision {
+    pub fn size(&self) -> usize {
+        site::routes_size(&self.routes)
+    }
+}
+
+
+pub struct RevisionDiff {
+    pub added: Vec<UrlPath>,
+    pub removed: Vec<UrlPath>,
+    pub changed: Vec<UrlPath>,
+}
+
+pub fn diff(from: &Revision, to: &Revision) -> RevisionDiff {
+    let added = to.routes
+        .keys()
+        .filter(|path| !from.routes.contains_key(path))
+        .cloned()
+        .collect();
+
+    let removed = from.routes
+        .keys()
+        .filter(|path| !to.routes.contains_key(path))
+        .cloned()
+        .collect();
+
+    let changed = to.routes
+        .iter()
+        .filter(|(path, route_info)| {
+            from.routes.get(path)
+                .map(|old_route_info| is_changed(old_route_info, route_info))
+                .unwrap_or(false)
+        })
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    RevisionDiff{
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn is_changed(old: &RouteInfo, new: &RouteInfo) -> bool {
+    match (old, new) {
+        (RouteInfo::File{file_info: old, headers: old_headers}, RouteInfo::File{file_info: new, headers: new_headers}) =>
+            old.hash != new.hash || old.mime != new.mime || old_headers != new_headers,
+
+        (RouteInfo::Redirect{redirect: old}, RouteInfo::Redirect{redirect: new}) =>
+            old.target != new.target || old.status != new.status,
+
+        _ =>
+            true,
+    }
+}
+
+
+pub enum GetRevisionError {
+    RevisionNotFound(),
+    ReadRevisionJson(file::ReadJsonError),
+}
+
+pub fn get(site_root: &SiteRoot, revision: u64) -> Result<Revision, GetRevisionError> {
+    storage::read_json(site_root.storage(), &site_root.revision_key(revision))
+        .map_err(|err| match err {
+            file::ReadJsonError::Open(err) if storage::is_not_found(&err) =>
+                GetRevisionError::RevisionNotFound(),
+
+            err =>
+                GetRevisionError::ReadRevisionJson(err),
+        })
+}
+
+
+pub enum ListRevisionsError {
+    ReadDir(io::Error),
+    ReadRevisionJson(file::ReadJsonError),
+}
+
+// Returns all stored revisions, newest first
+pub fn list(site_root: &SiteRoot) -> Result<Vec<Revision>, ListRevisionsError> {
+    let mut revisions = list_revision_numbers(site_root)
+        .map_err(ListRevisionsError::ReadDir)?
+        .iter()
+        .map(|revision| storage::read_json(site_root.storage(), &site_root.revision_key(*revision)))
+        .collect::<Result<Vec<Revision>, file::ReadJsonError>>()
+        .map_err(ListRevisionsError::ReadRevisionJson)?;
+
+    revisions.sort_by_key(|revision| cmp::Reverse(revision.revision));
+
+    Ok(revisions)
+}
+
+fn list_revision_numbers(site_root: &SiteRoot) -> Result<Vec<u64>, io::Error> {
+    let numbers = site_root.storage().list(&site_root.revisions_prefix())?
+        .iter()
+        .map(|object| PathBuf::from(&object.key))
+        .filter(|path| path.extension().map(|ext| ext == "json").unwrap_or(false))
+        .filter_map(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+        })
+        .collect();
+
+    Ok(numbers)
+}
+
+// Removes revisions that are older than the newest max_revisions revisions
+pub fn remove_expired(site_root: &SiteRoot, current_revision: u64, max_revisions: u64) -> Result<(), io::Error> {
+    let oldest_kept = current_revision.saturating_sub(max_revisions.max(1)) + 1;
+
+    list_revision_numbers(site_root)?
+        .iter()
+        .filter(|revision| **revision < oldest_kept)
+        .for_each(|revision| {
+            let _ = site_root.storage().remove(&site_root.revision_key(*revision));
+        });
+
+    Ok(())
+}
+
+
+pub enum RollbackError {
+    GetRevision(GetRevisionError),
+    QuotaMaxSize(),
+    QuotaMaxRoutes(),
+}
+
+impl Site {
+    // Restores the routes of an earlier revision, the rollback is stored as a new revision when persisted
+    pub fn rollback(&mut self, config: &Config, site_root: &SiteRoot, revision: u64) -> Result<&Site, RollbackError> {
+        let limits = self.quota.limits(config);
+
+        let old_revision = get(site_root, revision)
+            .map_err(RollbackError::GetRevision)?;
+
+        util::ensure(old_revision.size() < limits.max_size, RollbackError::QuotaMaxSize())?;
+        util::ensure(old_revision.routes.len() <= limits.max_routes, RollbackError::QuotaMaxRoutes())?;
+
+        self.routes = old_revision.routes;
+
+        Ok(self)
+    }
+
+    pub fn current_revision(&self, timestamp: u64) -> Revision {
+        Revision{
+            revision: self.revision,
+            timestamp,
+            routes: self.routes.clone(),
+        }
+    }
+}
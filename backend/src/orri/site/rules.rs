@@ -0,0 +1,560 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use crate::orri::redirect;
+use crate::orri::site::headers::Header;
+
+
+// Routes with these paths are parsed into rules when the site is persisted, they are not served
+pub const REDIRECTS_PATH: &str = "/_redirects";
+pub const HEADERS_PATH: &str = "/_headers";
+
+pub const MAX_FILE_SIZE: usize = 65536;
+const MAX_RULES: usize = 1000;
+
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct Rules {
+    pub redirects: Vec<RedirectRule>,
+    pub headers: Vec<HeaderRule>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct RedirectRule {
+    pub from: Pattern,
+    pub to: String,
+    pub status: RuleStatus,
+
+    // A forced rule also applies to paths that have a route
+    pub force: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct HeaderRule {
+    pub pattern: Pattern,
+    pub headers: Vec<Header>,
+}
+
+pub struct RedirectMatch<'a> {
+    pub rule: &'a RedirectRule,
+
+    // The target with placeholders and the splat replaced by the matched parts of the path
+    pub target: String,
+}
+
+impl Rules {
+    // The first matching rule wins, rules that are not forced only apply to paths without a route
+    pub fn find_redirect(&self, path: &str, has_route: bool) -> Option<RedirectMatch<'_>> {
+        self.redirects
+            .iter()
+            .filter(|rule| rule.force || !has_route)
+            .find_map(|rule| {
+                rule.from.matches(path).map(|captures| RedirectMatch{
+                    rule,
+                    target: substitute(&rule.to, &captures),
+                })
+            })
+    }
+
+    // All matching rules apply, a later rule overrides a header set by an earlier one
+    pub fn headers_for(&self, path: &str) -> Vec<Header> {
+        self.headers
+            .iter()
+            .filter(|rule| rule.pattern.matches(path).is_some())
+            .flat_map(|rule| rule.headers.iter().cloned())
+            .collect()
+    }
+}
+
+pub fn is_rules_path(path: &str) -> bool {
+    path == REDIRECTS_PATH || path == HEADERS_PATH
+}
+
+
+// Stored as the numeric status code like in the _redirects file
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub enum RuleStatus {
+    // The target is served at the requested path
+    Rewrite(),
+
+    // The target is served with status 404
+    NotFound(),
+
+    Redirect(redirect::Status),
+}
+
+impl From<RuleStatus> for u16 {
+    fn from(status: RuleStatus) -> u16 {
+        match status {
+            RuleStatus::Rewrite() =>
+                200,
+
+            RuleStatus::NotFound() =>
+                404,
+
+            RuleStatus::Redirect(status) =>
+                status.into(),
+        }
+    }
+}
+
+impl TryFrom<u16> for RuleStatus {
+    type Error = String;
+
+    fn try_from(code: u16) -> Result<RuleStatus, String> {
+        match code {
+            200 =>
+                Ok(RuleStatus::Rewrite()),
+
+            404 =>
+                Ok(RuleStatus::NotFound()),
+
+            code =>
+                redirect::Status::try_from(code)
+                    .map(RuleStatus::Redirect)
+                    .map_err(|_| format!("Unsupported status {}, expected 200, 301, 302, 307, 308 or 404", code)),
+        }
+    }
+}
+
+
+// A path where a segment can be a :placeholder, contain a * that matches within the segment,
+// or be a single * at the end that matches the rest of the path. Stored as the original string
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+    Glob(String, String),
+    Splat(),
+}
+
+impl Pattern {
+    // Returns the placeholders and the splat captured from the path.
+    // A trailing slash is ignored on both the pattern and the path
+    pub fn matches(&self, path: &str) -> Option<HashMap<String, String>> {
+        let path_segments = split_segments(path);
+        let mut captures = HashMap::new();
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let Segment::Splat() = segment {
+                captures.insert("splat".to_string(), path_segments[index.min(path_segments.len())..].join("/"));
+                return Some(captures);
+            }
+
+            let path_segment = path_segments.get(index)?;
+
+            match segment {
+                Segment::Literal(literal) if literal == path_segment =>
+                    (),
+
+                Segment::Placeholder(name) if !path_segment.is_empty() => {
+                    captures.insert(name.clone(), path_segment.to_string());
+                },
+
+                Segment::Glob(prefix, suffix) if path_segment.len() >= prefix.len() + suffix.len() && path_segment.starts_with(prefix.as_str()) && path_segment.ends_with(suffix.as_str()) => {
+                    let matched = &path_segment[prefix.len()..path_segment.len() - suffix.len()];
+                    captures.insert("splat".to_string(), matched.to_string());
+                },
+
+                _ =>
+                    return None,
+            }
+        }
+
+        if path_segments.len() == self.segments.len() {
+            Some(captures)
+        } else {
+            None
+        }
+    }
+}
+
+fn split_segments(path: &str) -> Vec<&str> {
+    let path = path.trim_start_matches('/');
+    let path = if path.is_empty() { path } else { path.trim_end_matches('/') };
+
+    path.split('/').collect()
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl From<Pattern> for String {
+    fn from(pattern: Pattern) -> String {
+        pattern.source
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Pattern, String> {
+        Pattern::from_str(&source)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Pattern, String> {
+        if !source.starts_with('/') {
+            return Err(format!("The path «{}» must start with a slash", source));
+        }
+
+        let parts = split_segments(source);
+        let last_index = parts.len() - 1;
+
+        let segments = parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| parse_segment(part, index == last_index))
+            .collect::<Result<Vec<Segment>, String>>()?;
+
+        Ok(Pattern{
+            source: source.to_string(),
+            segments,
+        })
+    }
+}
+
+fn parse_segment(part: &str, is_last: bool) -> Result<Segment, String> {
+    if part == "*" {
+        if !is_last {
+            return Err("A * that matches the rest of the path is only allowed at the end".to_string());
+        }
+
+        return Ok(Segment::Splat());
+    }
+
+    if let Some(name) = part.strip_prefix(':') {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid placeholder «{}»", part));
+        }
+
+        return Ok(Segment::Placeholder(name.to_string()));
+    }
+
+    match part.matches('*').count() {
+        0 =>
+            Ok(Segment::Literal(part.to_string())),
+
+        1 => {
+            let mut glob = part.splitn(2, '*');
+            let prefix = glob.next().unwrap_or_default();
+            let suffix = glob.next().unwrap_or_default();
+
+            Ok(Segment::Glob(prefix.to_string(), suffix.to_string()))
+        },
+
+        _ =>
+            Err(format!("Only one * is allowed in «{}»", part)),
+    }
+}
+
+// Replaces :splat and :name with the captured values, unknown names are kept as is
+fn substitute(target: &str, captures: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = target;
+
+    while let Some(index) = rest.find(':') {
+        result.push_str(&rest[..index]);
+
+        let after_colon = &rest[index + 1..];
+        let name_length = after_colon
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after_colon.len());
+
+        match captures.get(&after_colon[..name_length]) {
+            Some(value) if name_length > 0 =>
+                result.push_str(value),
+
+            _ => {
+                result.push(':');
+                result.push_str(&after_colon[..name_length]);
+            },
+        }
+
+        rest = &after_colon[name_length..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+
+pub struct RuleError {
+    pub file: &'static str,
+
+    // 0 when the error is about the whole file
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.file, self.message)
+        } else {
+            write!(f, "{} line {}: {}", self.file, self.line, self.message)
+        }
+    }
+}
+
+pub fn format_errors(errors: &[RuleError]) -> String {
+    errors
+        .iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+
+// All errors are collected so they can be fixed in one go
+pub fn parse(redirects: Option<&str>, headers: Option<&str>) -> Result<Rules, Vec<RuleError>> {
+    let mut errors = vec![];
+
+    let redirect_rules = redirects
+        .map(|text| parse_redirects(text, &mut errors))
+        .unwrap_or_default();
+
+    let header_rules = headers
+        .map(|text| parse_headers(text, &mut errors))
+        .unwrap_or_default();
+
+    if redirect_rules.len() > MAX_RULES {
+        errors.push(file_error(REDIRECTS_PATH, format!("More than {} rules", MAX_RULES)));
+    }
+
+    if header_rules.len() > MAX_RULES {
+        errors.push(file_error(HEADERS_PATH, format!("More than {} rules", MAX_RULES)));
+    }
+
+    if errors.is_empty() {
+        Ok(Rules{
+            redirects: redirect_rules,
+            headers: header_rules,
+        })
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn file_error(file: &'static str, message: String) -> RuleError {
+    RuleError{
+        file,
+        line: 0,
+        message,
+    }
+}
+
+// One rule per line: <from> <to> [status][!]
+fn parse_redirects(text: &str, errors: &mut Vec<RuleError>) -> Vec<RedirectRule> {
+    content_lines(text)
+        .filter_map(|(line, raw)| {
+            parse_redirect_line(raw.trim())
+                .map_err(|message| errors.push(RuleError{ file: REDIRECTS_PATH, line, message }))
+                .ok()
+        })
+        .collect()
+}
+
+fn parse_redirect_line(content: &str) -> Result<RedirectRule, String> {
+    let fields = content.split_whitespace().collect::<Vec<&str>>();
+
+    let (from, to, status) = match fields.as_slice() {
+        [from, to] =>
+            (from, to, "301"),
+
+        [from, to, status] =>
+            (from, to, *status),
+
+        _ =>
+            return Err("Expected a path, a target and an optional status".to_string()),
+    };
+
+    let from = Pattern::from_str(from)?;
+    let force = status.ends_with('!');
+
+    let status = status.trim_end_matches('!')
+        .parse::<u16>()
+        .map_err(|_| format!("Invalid status «{}»", status))
+        .and_then(RuleStatus::try_from)?;
+
+    match status {
+        RuleStatus::Redirect(_) => {
+            redirect::Target::from_str(to)
+                .map_err(|_| format!("The target «{}» must be a path starting with a slash or a http(s) url", to))?;
+        },
+
+        RuleStatus::Rewrite() | RuleStatus::NotFound() => {
+            if !to.starts_with('/') || to.starts_with("//") {
+                return Err(format!("The target «{}» of a rewrite must be a path on the site", to));
+            }
+        },
+    }
+
+    Ok(RedirectRule{
+        from,
+        to: to.to_string(),
+        status,
+        force,
+    })
+}
+
+// A path on its own line followed by indented "Name: value" lines
+fn parse_headers(text: &str, errors: &mut Vec<RuleError>) -> Vec<HeaderRule> {
+    let mut rules: Vec<HeaderRule> = vec![];
+
+    for (line, raw) in content_lines(text) {
+        let content = raw.trim();
+
+        if !raw.starts_with(char::is_whitespace) {
+            match Pattern::from_str(content) {
+                Ok(pattern) =>
+                    rules.push(HeaderRule{ pattern, headers: vec![] }),
+
+                Err(message) =>
+                    errors.push(RuleError{ file: HEADERS_PATH, line, message }),
+            }
+
+            continue;
+        }
+
+        let header = Header::from_str(content)
+            .map_err(|err| err.to_string());
+
+        match (rules.last_mut(), header) {
+            (Some(rule), Ok(header)) =>
+                rule.headers.push(header),
+
+            (None, _) =>
+                errors.push(RuleError{ file: HEADERS_PATH, line, message: "A header must come after a path".to_string() }),
+
+            (_, Err(message)) =>
+                errors.push(RuleError{ file: HEADERS_PATH, line, message }),
+        }
+    }
+
+    rules
+}
+
+// Line numbers start at 1, empty lines and comments are skipped.
+// The lines are not trimmed since indentation is significant in the _headers file
+fn content_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_redirect_rules(text: &str) -> Rules {
+        parse(Some(text), None)
+            .unwrap_or_else(|errors| panic!("{}", format_errors(&errors)))
+    }
+
+    fn redirect_target(rules: &Rules, path: &str, has_route: bool) -> Option<(String, u16)> {
+        rules.find_redirect(path, has_route)
+            .map(|found| (found.target, found.rule.status.into()))
+    }
+
+    fn error_lines(result: Result<Rules, Vec<RuleError>>) -> Vec<(&'static str, usize)> {
+        result
+            .err()
+            .unwrap_or_default()
+            .iter()
+            .map(|err| (err.file, err.line))
+            .collect()
+    }
+
+    #[test]
+    fn first_matching_redirect_wins() {
+        let rules = parse_redirect_rules("/docs/intro /guide 302\n/docs/* /manual/:splat\n/docs/intro /never\n");
+
+        assert_eq!(redirect_target(&rules, "/docs/intro", false), Some(("/guide".to_string(), 302)));
+        assert_eq!(redirect_target(&rules, "/docs/a/b", false), Some(("/manual/a/b".to_string(), 301)));
+        assert_eq!(redirect_target(&rules, "/other", false), None);
+    }
+
+    #[test]
+    fn only_forced_rules_apply_to_existing_routes() {
+        let rules = parse_redirect_rules("/old /new\n/moved /elsewhere 301!\n");
+
+        assert_eq!(redirect_target(&rules, "/old", true), None);
+        assert_eq!(redirect_target(&rules, "/old", false), Some(("/new".to_string(), 301)));
+        assert_eq!(redirect_target(&rules, "/moved", true), Some(("/elsewhere".to_string(), 301)));
+    }
+
+    #[test]
+    fn substitutes_placeholders_and_globs() {
+        let rules = parse_redirect_rules("# comment\n\n/blog/:year/:slug /posts/:year-:slug 200\n/img/*.png /images/:splat.webp\n");
+
+        assert_eq!(redirect_target(&rules, "/blog/2020/hello/", false), Some(("/posts/2020-hello".to_string(), 200)));
+        assert_eq!(redirect_target(&rules, "/img/logo.png", false), Some(("/images/logo.webp".to_string(), 301)));
+        assert_eq!(redirect_target(&rules, "/blog/2020", false), None);
+    }
+
+    #[test]
+    fn later_header_rules_come_last() {
+        let rules = parse(None, Some("/*\n  X-Frame-Options: DENY\n/embed/*\n  X-Frame-Options: SAMEORIGIN\n"))
+            .unwrap_or_else(|errors| panic!("{}", format_errors(&errors)));
+
+        let values = |path| rules.headers_for(path)
+            .into_iter()
+            .map(|header| header.value)
+            .collect::<Vec<String>>();
+
+        assert_eq!(values("/index.html"), vec!["DENY"]);
+        assert_eq!(values("/embed/video"), vec!["DENY", "SAMEORIGIN"]);
+    }
+
+    #[test]
+    fn reports_every_malformed_redirect_line() {
+        let text = "/ok /fine\n/missing-target\nrelative /target\n/bad-status /target 303\n/rewrite https://example.com 200\n/a/*/b /c\n/:bad-name /c\n";
+
+        assert_eq!(error_lines(parse(Some(text), None)), vec![
+            (REDIRECTS_PATH, 2),
+            (REDIRECTS_PATH, 3),
+            (REDIRECTS_PATH, 4),
+            (REDIRECTS_PATH, 5),
+            (REDIRECTS_PATH, 6),
+            (REDIRECTS_PATH, 7),
+        ]);
+    }
+
+    #[test]
+    fn reports_malformed_header_lines() {
+        let text = "  X-Frame-Options: DENY\n/*\n  Set-Cookie: a=b\n  no colon\n  X-Robots-Tag: noindex\n";
+
+        assert_eq!(error_lines(parse(None, Some(text))), vec![
+            (HEADERS_PATH, 1),
+            (HEADERS_PATH, 3),
+            (HEADERS_PATH, 4),
+        ]);
+    }
+
+    #[test]
+    fn rejects_too_many_rules() {
+        let text = "/a /b\n".repeat(MAX_RULES + 1);
+
+        assert_eq!(error_lines(parse(Some(&text), None)), vec![(REDIRECTS_PATH, 0)]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt;
+use serde_json::{json, Value, Map};
+
+
+// Bump this and add a migration to MIGRATIONS when the format of site.json changes
+pub const CURRENT_VERSION: u64 = 8;
+
+// Documents written before the schema version was introduced
+const INITIAL_VERSION: u64 = 1;
+
+type Migration = fn(Map<String, Value>) -> Result<Map<String, Value>, Error>;
+
+// The migration at index n upgrades a document from version n + 1 to version n + 2
+const MIGRATIONS: &[Migration] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+];
+
+
+pub enum Error {
+    NotAnObject(),
+    InvalidVersion(),
+    UnsupportedVersion(u64),
+    MissingMigration(u64),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotAnObject() =>
+                write!(f, "The site document is not a json object"),
+
+            Error::InvalidVersion() =>
+                write!(f, "The schema_version field is not a number"),
+
+            Error::UnsupportedVersion(version) =>
+                write!(f, "Schema version {} is newer than the supported version {}", version, CURRENT_VERSION),
+
+            Error::MissingMigration(version) =>
+                write!(f, "No migration found for schema version {}", version),
+        }
+    }
+}
+
+
+pub struct Migrated {
+    pub document: Value,
+    pub from_version: u64,
+}
+
+impl Migrated {
+    pub fn is_changed(&self) -> bool {
+        self.from_version != CURRENT_VERSION
+    }
+}
+
+
+// Upgrades a site document to the current schema version by running every migration in order
+pub fn migrate(document: Value) -> Result<Migrated, Error> {
+    let mut object = match document {
+        Value::Object(object) =>
+            object,
+
+        _ =>
+            return Err(Error::NotAnObject()),
+    };
+
+    let from_version = get_version(&object)?;
+
+    if from_version > CURRENT_VERSION {
+        return Err(Error::UnsupportedVersion(from_version));
+    }
+
+    for version in from_version..CURRENT_VERSION {
+        let migration = MIGRATIONS.get((version - INITIAL_VERSION) as usize)
+            .ok_or(Error::MissingMigration(version))?;
+
+        object = migration(object)?;
+        object.insert("schema_version".to_string(), Value::from(version + 1));
+    }
+
+    Ok(Migrated{
+        document: Value::Object(object),
+        from_version,
+    })
+}
+
+fn get_version(object: &Map<String, Value>) -> Result<u64, Error> {
+    match object.get("schema_version") {
+        Some(value) =>
+            value.as_u64().ok_or(Error::InvalidVersion()),
+
+        None =>
+            Ok(INITIAL_VERSION),
+    }
+}
+
+
+// Version 2 introduced the schema version and the revision counter
+fn migrate_v1_to_v2(mut object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    object.entry("revision")
+        .or_insert_with(|| Value::from(0));
+
+    Ok(object)
+}
+
+// Version 3 introduced redirect routes, a route holds either file_info or redirect.
+// Existing file routes are already in that format, the version is bumped so older
+// releases reject documents with redirects instead of failing to parse the routes
+fn migrate_v2_to_v3(object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    Ok(object)
+}
+
+// Version 4 introduced the site settings, existing sites get the defaults
+fn migrate_v3_to_v4(mut object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    object.entry("settings")
+        .or_insert_with(|| Value::Object(Map::new()));
+
+    Ok(object)
+}
+
+// Version 5 introduced the rules parsed from the _redirects and _headers routes.
+// The routes are only parsed when the site is persisted, existing sites have no rules until then
+fn migrate_v4_to_v5(mut object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    object.entry("rules")
+        .or_insert_with(|| json!({ "redirects": [], "headers": [] }));
+
+    Ok(object)
+}
+
+// Version 6 introduced custom headers on file routes and in the settings, both are optional.
+// The versions 6 to 8 are bumped so older releases reject documents with the new fields
+// instead of dropping them when the site is written
+fn migrate_v5_to_v6(object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    Ok(object)
+}
+
+// Version 7 introduced api tokens, a site without tokens has no tokens field
+fn migrate_v6_to_v7(object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    Ok(object)
+}
+
+// Version 8 introduced collaborators, a site without collaborators has no collaborators field
+fn migrate_v7_to_v8(object: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+    Ok(object)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orri::site::Site;
+
+    // A site.json as written before the schema version existed
+    fn v1_document() -> Value {
+        json!({
+            "domain": "test.orri.dev",
+            "key": "$argon2i$v=19$m=4096,t=3,p=1$c2FsdA$aGFzaA",
+            "quota": "nano",
+            "routes": {
+                "/": {
+                    "file_info": {
+                        "mime": "text/html",
+                        "hash": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                        "size": 0,
+                        "timestamp": 1590000000
+                    }
+                }
+            }
+        })
+    }
+
+    fn migrate_ok(document: Value) -> Migrated {
+        migrate(document)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    #[test]
+    fn migrates_v1_to_the_current_version() {
+        let migrated = migrate_ok(v1_document());
+
+        assert_eq!(migrated.from_version, INITIAL_VERSION);
+        assert!(migrated.is_changed());
+        assert_eq!(migrated.document["schema_version"], json!(CURRENT_VERSION));
+        assert_eq!(migrated.document["revision"], json!(0));
+        assert_eq!(migrated.document["routes"], v1_document()["routes"]);
+    }
+
+    #[test]
+    fn migrated_v1_site_round_trips() {
+        let site: Site = serde_json::from_value(migrate_ok(v1_document()).document)
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        assert_eq!(site.revision, 0);
+        assert_eq!(site.schema_version, CURRENT_VERSION);
+        assert_eq!(site.routes.len(), 1);
+
+        let written = serde_json::to_value(&site)
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        let migrated = migrate_ok(written.clone());
+
+        assert!(!migrated.is_changed());
+        assert_eq!(migrated.document, written);
+    }
+
+    #[test]
+    fn migrates_v3_to_the_current_version() {
+        let mut document = v1_document();
+        document["schema_version"] = json!(3);
+        document["revision"] = json!(2);
+
+        let migrated = migrate_ok(document);
+
+        assert_eq!(migrated.from_version, 3);
+        assert_eq!(migrated.document["settings"], json!({}));
+        assert_eq!(migrated.document["rules"], json!({ "redirects": [], "headers": [] }));
+
+        let site: Site = serde_json::from_value(migrated.document)
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        assert!(site.settings.fallback_route.is_none());
+        assert!(site.rules.redirects.is_empty());
+        assert!(site.tokens.is_empty());
+        assert!(site.collaborators.is_empty());
+    }
+
+    #[test]
+    fn keeps_existing_settings_and_rules() {
+        let mut document = v1_document();
+        document["schema_version"] = json!(3);
+        document["settings"] = json!({ "trailingSlash": "add" });
+        document["rules"] = json!({ "redirects": [], "headers": [{ "path": "/*", "headers": [] }] });
+
+        let migrated = migrate_ok(document.clone());
+
+        assert_eq!(migrated.document["settings"], document["settings"]);
+        assert_eq!(migrated.document["rules"], document["rules"]);
+    }
+
+    #[test]
+    fn keeps_an_existing_revision() {
+        let mut document = v1_document();
+        document["revision"] = json!(7);
+
+        assert_eq!(migrate_ok(document).document["revision"], json!(7));
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert!(matches!(migrate(json!([1, 2])), Err(Error::NotAnObject())));
+        assert!(matches!(migrate(json!({ "schema_version": "2" })), Err(Error::InvalidVersion())));
+        assert!(matches!(migrate(json!({ "schema_version": -1 })), Err(Error::InvalidVersion())));
+        assert!(matches!(migrate(json!({ "schema_version": CURRENT_VERSION + 1 })), Err(Error::UnsupportedVersion(version)) if version == CURRENT_VERSION + 1));
+    }
+
+    #[test]
+    fn has_a_migration_for_every_version() {
+        assert_eq!(MIGRATIONS.len() as u64, CURRENT_VERSION - INITIAL_VERSION);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::orri::url_path::UrlPath;
+use crate::orri::site::headers::Header;
+
+
+// Per site options for how requests are served, sites created before settings existed get the defaults
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub trailing_slash: TrailingSlash,
+
+    // Routes served instead of an empty body, with the original status code, when a request fails
+    pub not_found_page: Option<UrlPath>,
+    pub error_page: Option<UrlPath>,
+
+    // Served with status 200 for paths that have no route, for apps with client side routing
+    pub fallback_route: Option<UrlPath>,
+    pub fallback_exclude: FallbackExclude,
+
+    // Sent with every response, the _headers file and the headers of a route override them
+    pub headers: Vec<Header>,
+}
+
+
+// Which of /docs and /docs/ is the canonical url when only one of them, or the
+// directory index /docs/index.html, exists as a route
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    // Both forms are served without redirecting
+    #[default]
+    Ignore,
+
+    // /docs is redirected to /docs/
+    Add,
+
+    // /docs/ is redirected to /docs
+    Remove,
+}
+
+
+impl fmt::Display for TrailingSlash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrailingSlash::Ignore =>
+                write!(f, "ignore"),
+
+            TrailingSlash::Add =>
+                write!(f, "add"),
+
+            TrailingSlash::Remove =>
+                write!(f, "remove"),
+        }
+    }
+}
+
+
+// Paths that get a 404 instead of the fallback route, i.e. missing assets like /app.js
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct FallbackExclude {
+    pub prefixes: Vec<String>,
+
+    // Stored without the leading dot
+    pub extensions: Vec<String>,
+}
+
+impl FallbackExclude {
+    pub fn matches(&self, path: &UrlPath) -> bool {
+        let path_str = path.to_string();
+
+        let has_excluded_prefix = self.prefixes
+            .iter()
+            .any(|prefix| path_str.starts_with(prefix));
+
+        let has_excluded_extension = path.relative_path()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| self.extensions.iter().any(|excluded| excluded.eq_ignore_ascii_case(extension)))
+            .unwrap_or(false);
+
+        has_excluded_prefix || has_excluded_extension
+    }
+}
+
+// The same format as parsed by from_str, i.e. "/api/, .js, .css"
+impl fmt::Display for FallbackExclude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self.prefixes
+            .iter()
+            .cloned()
+            .chain(self.extensions.iter().map(|extension| format!(".{}", extension)))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+
+pub enum FallbackExcludeError {
+    InvalidPrefix(String),
+    InvalidExtension(String),
+}
+
+impl fmt::Display for FallbackExcludeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FallbackExcludeError::InvalidPrefix(s) =>
+                write!(f, "Invalid fallback exclusion «{}», a prefix must be a valid path like /api/", s),
+
+            FallbackExcludeError::InvalidExtension(s) =>
+                write!(f, "Invalid fallback exclusion «{}», an extension must be alphanumeric like .js", s),
+        }
+    }
+}
+
+// A comma separated list where entries starting with a slash are prefixes and entries starting with a dot are extensions
+impl FromStr for FallbackExclude {
+    type Err = FallbackExcludeError;
+
+    fn from_str(s: &str) -> Result<FallbackExclude, FallbackExcludeError> {
+        let mut exclude = FallbackExclude::default();
+
+        let entries = s
+            .split(',')
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty());
+
+        for entry in entries {
+            if entry.starts_with('/') {
+                UrlPath::from_str(entry)
+                    .map_err(|_| FallbackExcludeError::InvalidPrefix(entry.to_string()))?;
+
+                exclude.prefixes.push(entry.to_string());
+            } else {
+                let extension = entry.trim_start_matches('.');
+                let is_valid = !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric());
+
+                if !is_valid {
+                    return Err(FallbackExcludeError::InvalidExtension(entry.to_string()));
+                }
+
+                exclude.extensions.push(extension.to_string());
+            }
+        }
+
+        Ok(exclude)
+    }
+}
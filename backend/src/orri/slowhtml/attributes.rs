@@ -111,10 +111,18 @@ pub fn title(value: &str) -> Attribute {
     attribute_trusted_name("title", value)
 }
 
+pub fn rows(value: &str) -> Attribute {
+    attribute_trusted_name("rows", value)
+}
+
 pub fn pattern(value: &str) -> Attribute {
     attribute_trusted_name("pattern", value)
 }
 
+pub fn min(value: &str) -> Attribute {
+    attribute_trusted_name("min", value)
+}
+
 pub fn readonly() -> Attribute {
     bool_attribute_trusted("readonly")
 }
@@ -123,6 +131,10 @@ pub fn required() -> Attribute {
     bool_attribute_trusted("required")
 }
 
+pub fn selected() -> Attribute {
+    bool_attribute_trusted("selected")
+}
+
 pub fn class_list(list: &[(&str, bool)]) -> Attribute {
     let classes = list
         .iter()
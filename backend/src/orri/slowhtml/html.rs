@@ -239,6 +239,18 @@ pub fn input(attributes: &[Attribute]) -> Html {
     node_no_end_trusted_name("input", attributes)
 }
 
+pub fn select(attributes: &[Attribute], children: &[Html]) -> Html {
+    node_trusted_name("select", attributes, children)
+}
+
+pub fn option(attributes: &[Attribute], children: &[Html]) -> Html {
+    node_trusted_name("option", attributes, children)
+}
+
+pub fn textarea(attributes: &[Attribute], children: &[Html]) -> Html {
+    node_trusted_name("textarea", attributes, children)
+}
+
 pub fn meta(attributes: &[Attribute]) -> Html {
     node_no_end_trusted_name("meta", attributes)
 }
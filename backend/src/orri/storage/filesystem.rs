@@ -0,0 +1,184 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
+use crate::orri::storage::{SiteStorage, ObjectInfo, ObjectReader};
+
+
+// Stores everything below the sites root, keys map directly to relative paths
+#[derive(Debug)]
+pub struct FilesystemStorage {
+    root: PathBuf,
+}
+
+impl FilesystemStorage {
+    pub fn new(root: &Path) -> FilesystemStorage {
+        FilesystemStorage{
+            root: root.to_path_buf(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(PathBuf::from(key))
+    }
+
+    fn prepare_parent_dir(&self, path: &Path) -> Result<(), io::Error> {
+        match path.parent() {
+            Some(dir) =>
+                fs::create_dir_all(dir),
+
+            None =>
+                Ok(()),
+        }
+    }
+}
+
+impl SiteStorage for FilesystemStorage {
+    fn read(&self, key: &str) -> Result<Vec<u8>, io::Error> {
+        fs::read(self.path(key))
+    }
+
+    fn open(&self, key: &str) -> Result<ObjectReader, io::Error> {
+        let file = fs::File::open(self.path(key))?;
+        let size = file.metadata()?.len();
+
+        Ok(ObjectReader{
+            reader: Box::new(file),
+            size,
+        })
+    }
+
+    fn open_range(&self, key: &str, offset: u64, length: u64) -> Result<ObjectReader, io::Error> {
+        let mut file = fs::File::open(self.path(key))?;
+        let file_size = file.metadata()?.len();
+        let size = length.min(file_size.saturating_sub(offset));
+
+        file.seek(SeekFrom::Start(offset))?;
+
+        Ok(ObjectReader{
+            reader: Box::new(file.take(size)),
+            size,
+        })
+    }
+
+    // Writes to a temp file that is renamed into place so readers never see a partial file
+    fn write(&self, key: &str, data: &[u8]) -> Result<(), io::Error> {
+        let path = self.path(key);
+
+        self.prepare_parent_dir(&path)?;
+
+        let dir = path.parent()
+            .unwrap_or(&self.root);
+
+        let mut file = NamedTempFile::new_in(dir)?;
+        file.write_all(data)?;
+
+        file.persist(&path)
+            .map_err(|err| err.error)?;
+
+        Ok(())
+    }
+
+    fn write_file(&self, key: &str, file: NamedTempFile) -> Result<(), io::Error> {
+        let path = self.path(key);
+
+        self.prepare_parent_dir(&path)?;
+
+        file.persist(&path)
+            .map_err(|err| err.error)?;
+
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> Result<bool, io::Error> {
+        Ok(self.path(key).exists())
+    }
+
+    fn modified(&self, key: &str) -> Result<SystemTime, io::Error> {
+        fs::metadata(self.path(key))?.modified()
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>, io::Error> {
+        let path = self.path(prefix);
+        let mut objects = vec![];
+
+        if path.is_dir() {
+            list_files(&self.root, &path, &mut objects)?;
+        }
+
+        Ok(objects)
+    }
+
+    fn list_dirs(&self, prefix: &str) -> Result<Vec<String>, io::Error> {
+        let path = self.path(prefix);
+
+        if !path.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let dirs = fs::read_dir(path)?
+            .filter_map(|res| res.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+
+        Ok(dirs)
+    }
+
+    fn remove(&self, key: &str) -> Result<(), io::Error> {
+        fs::remove_file(self.path(key))
+    }
+
+    fn remove_all(&self, prefix: &str) -> Result<(), io::Error> {
+        let path = self.path(prefix);
+
+        if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            Ok(())
+        }
+    }
+
+    // The temp dir is below the root so temp files can be renamed into place
+    fn temp_dir(&self) -> PathBuf {
+        self.root.join(PathBuf::from("blobs"))
+    }
+
+    fn local_path(&self, key: &str) -> Option<PathBuf> {
+        Some(self.path(key))
+    }
+}
+
+
+// Temp files are hidden files and are not listed
+fn list_files(root: &Path, dir: &Path, objects: &mut Vec<ObjectInfo>) -> Result<(), io::Error> {
+    for res in fs::read_dir(dir)? {
+        let entry = res?;
+        let path = entry.path();
+
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            list_files(root, &path, objects)?;
+            continue;
+        }
+
+        let key = path.strip_prefix(root)
+            .map(|relative_path| relative_path.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let modified = entry.metadata()
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or_else(|_| SystemTime::now());
+
+        objects.push(ObjectInfo{
+            key,
+            modified,
+        });
+    }
+
+    Ok(())
+}
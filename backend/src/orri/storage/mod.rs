@@ -0,0 +1,144 @@
+pub mod filesystem;
+pub mod s3;
+
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{self, Read};
+use std::fmt;
+use std::sync::Arc;
+use std::str::FromStr;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
+use crate::orri::file::{ReadJsonError, WriteJsonError};
+
+
+// Where site documents, revisions and blobs are stored. Keys are slash separated,
+// i.e. <domain>/site.json, <domain>/revisions/<n>.json and blobs/aa/bb/<hash>
+pub trait SiteStorage: Send + Sync + fmt::Debug {
+    // Returns an error of kind NotFound if the key does not exist
+    fn read(&self, key: &str) -> Result<Vec<u8>, io::Error>;
+
+    // Opens the object so it can be read in chunks, i.e. when it's streamed to a client
+    fn open(&self, key: &str) -> Result<ObjectReader, io::Error>;
+
+    // Opens length bytes starting at offset without reading the rest of the object
+    fn open_range(&self, key: &str, offset: u64, length: u64) -> Result<ObjectReader, io::Error>;
+
+    fn write(&self, key: &str, data: &[u8]) -> Result<(), io::Error>;
+
+    // Stores the content of a temp file created in temp_dir()
+    fn write_file(&self, key: &str, file: NamedTempFile) -> Result<(), io::Error>;
+
+    fn exists(&self, key: &str) -> Result<bool, io::Error>;
+
+    // Returns an error of kind NotFound if the key does not exist
+    fn modified(&self, key: &str) -> Result<SystemTime, io::Error>;
+
+    // All objects with a key that starts with the given prefix
+    fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>, io::Error>;
+
+    // The names of the direct children of the given prefix that has children of their own
+    fn list_dirs(&self, prefix: &str) -> Result<Vec<String>, io::Error>;
+
+    fn remove(&self, key: &str) -> Result<(), io::Error>;
+
+    fn remove_all(&self, prefix: &str) -> Result<(), io::Error>;
+
+    // Local dir for temp files, i.e. uploads that are streamed to disk before they are stored
+    fn temp_dir(&self) -> PathBuf;
+
+    // Local path for the given key if the storage is backed by the local filesystem
+    fn local_path(&self, key: &str) -> Option<PathBuf>;
+}
+
+pub type Storage = Arc<dyn SiteStorage>;
+
+
+pub struct ObjectInfo {
+    pub key: String,
+    pub modified: SystemTime,
+}
+
+
+pub struct ObjectReader {
+    pub reader: Box<dyn Read + Send>,
+    pub size: u64,
+}
+
+
+
+#[derive(Clone, Debug)]
+pub enum Config {
+    Filesystem(),
+    S3(s3::Config),
+}
+
+pub fn new(config: &Config, sites_root: &Path) -> Storage {
+    match config {
+        Config::Filesystem() =>
+            Arc::new(filesystem::FilesystemStorage::new(sites_root)),
+
+        Config::S3(s3_config) =>
+            Arc::new(s3::S3Storage::new(s3_config.clone())),
+    }
+}
+
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Backend {
+    Filesystem(),
+    S3(),
+}
+
+pub enum BackendFromStrError {
+    UnknownBackend(String)
+}
+
+impl fmt::Display for BackendFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackendFromStrError::UnknownBackend(s) =>
+                write!(f, "Unknown storage backend «{0}»", s),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = BackendFromStrError;
+
+    fn from_str(s: &str) -> Result<Backend, BackendFromStrError> {
+        match s {
+            "filesystem" =>
+                Ok(Backend::Filesystem()),
+
+            "s3" =>
+                Ok(Backend::S3()),
+
+            value =>
+                Err(BackendFromStrError::UnknownBackend(value.to_string())),
+        }
+    }
+}
+
+
+pub fn read_json<T: DeserializeOwned>(storage: &Storage, key: &str) -> Result<T, ReadJsonError> {
+    let data = storage.read(key)
+        .map_err(ReadJsonError::Open)?;
+
+    serde_json::from_slice(&data)
+        .map_err(ReadJsonError::Deserialize)
+}
+
+pub fn write_json<T: Serialize>(storage: &Storage, key: &str, value: &T) -> Result<(), WriteJsonError> {
+    let data = serde_json::to_vec_pretty(value)
+        .map_err(WriteJsonError::Serialize)?;
+
+    storage.write(key, &data)
+        .map_err(WriteJsonError::Persist)
+}
+
+pub fn is_not_found(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::NotFound
+}
@@ -0,0 +1,749 @@
+use std::io::{self, Read};
+use std::env;
+use std::path::PathBuf;
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+use ring::hmac;
+use chrono::{DateTime, Utc};
+use crate::orri::util;
+use crate::orri::storage::{SiteStorage, ObjectInfo, ObjectReader};
+
+
+// S3 doesn't require the payload to be signed, this lets files be streamed without hashing them twice
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+
+// Stores objects in a S3 compatible object store, i.e. AWS S3 or MinIO.
+// Requests use path style addressing: <endpoint>/<bucket>/<key>
+// A bucket must only be used by a single server instance. Site writes are locked within
+// the process and site.json is written without a conditional request, so two instances
+// writing the same site would overwrite each other's changes
+#[derive(Debug)]
+pub struct S3Storage {
+    config: Config,
+    temp_dir: PathBuf,
+}
+
+impl S3Storage {
+    pub fn new(config: Config) -> S3Storage {
+        S3Storage{
+            config,
+            temp_dir: env::temp_dir(),
+        }
+    }
+
+    fn object_path(&self, key: &str) -> String {
+        format!("/{}/{}", uri_encode(&self.config.bucket, false), uri_encode(key, false))
+    }
+
+    fn bucket_path(&self) -> String {
+        format!("/{}", uri_encode(&self.config.bucket, false))
+    }
+
+    fn request(&self, method: &str, path: &str, query: &[(&str, &str)]) -> ureq::Request {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let host = endpoint_host(&self.config.endpoint);
+        let query_string = canonical_query_string(query);
+
+        let signed_headers = [
+            ("host", host.as_str()),
+            ("x-amz-content-sha256", UNSIGNED_PAYLOAD),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+
+        let canonical_request = canonical_request(method, path, &query_string, &signed_headers, UNSIGNED_PAYLOAD);
+        let scope = credential_scope(&date, &self.config.region);
+        let string_to_sign = string_to_sign(&amz_date, &scope, &canonical_request);
+        let signature = signature(&self.config.secret_key, &date, &self.config.region, &string_to_sign);
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key, scope, signed_header_names(&signed_headers), signature
+        );
+
+        let url = if query_string.is_empty() {
+            format!("{}{}", self.config.endpoint.trim_end_matches('/'), path)
+        } else {
+            format!("{}{}?{}", self.config.endpoint.trim_end_matches('/'), path, query_string)
+        };
+
+        let mut request = ureq::request(method, &url);
+
+        request
+            .set("Host", &host)
+            .set("X-Amz-Date", &amz_date)
+            .set("X-Amz-Content-Sha256", UNSIGNED_PAYLOAD)
+            .set("Authorization", &authorization);
+
+        request
+    }
+
+    fn list_objects(&self, prefix: &str, delimiter: Option<&str>) -> Result<Vec<String>, io::Error> {
+        let mut bodies = vec![];
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+
+            if let Some(delimiter) = delimiter {
+                query.push(("delimiter", delimiter));
+            }
+
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.as_str()));
+            }
+
+            let response = self.request("GET", &self.bucket_path(), &query).call();
+            let response = ensure_success(response, "list", prefix)?;
+            let body = response.into_string()?;
+
+            let is_truncated = xml_elements(&body, "IsTruncated")
+                .first()
+                .map(|value| *value == "true")
+                .unwrap_or(false);
+
+            continuation_token = xml_elements(&body, "NextContinuationToken")
+                .first()
+                .map(|token| xml_unescape(token));
+
+            bodies.push(body);
+
+            if !is_truncated || continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(bodies)
+    }
+}
+
+impl SiteStorage for S3Storage {
+    fn read(&self, key: &str) -> Result<Vec<u8>, io::Error> {
+        let response = self.request("GET", &self.object_path(key), &[]).call();
+        let response = ensure_success(response, "read", key)?;
+        let mut data = vec![];
+
+        io::copy(&mut response.into_reader(), &mut data)?;
+
+        Ok(data)
+    }
+
+    fn open(&self, key: &str) -> Result<ObjectReader, io::Error> {
+        let response = self.request("GET", &self.object_path(key), &[]).call();
+        let response = ensure_success(response, "read", key)?;
+
+        object_reader(response, key)
+    }
+
+    fn open_range(&self, key: &str, offset: u64, length: u64) -> Result<ObjectReader, io::Error> {
+        // A range can't be empty, so nothing is requested
+        if length == 0 {
+            return Ok(ObjectReader{
+                reader: Box::new(io::empty()),
+                size: 0,
+            });
+        }
+
+        let range = format!("bytes={}-{}", offset, offset + length - 1);
+
+        let response = self.request("GET", &self.object_path(key), &[])
+            .set("Range", &range)
+            .call();
+
+        let response = ensure_success(response, "read", key)?;
+
+        object_reader(response, key)
+    }
+
+    fn write(&self, key: &str, data: &[u8]) -> Result<(), io::Error> {
+        let response = self.request("PUT", &self.object_path(key), &[])
+            .send_bytes(data);
+
+        ensure_success(response, "write", key)?;
+
+        Ok(())
+    }
+
+    fn write_file(&self, key: &str, file: NamedTempFile) -> Result<(), io::Error> {
+        let reader = file.reopen()?;
+        let size = reader.metadata()?.len();
+
+        let response = self.request("PUT", &self.object_path(key), &[])
+            .set("Content-Length", &size.to_string())
+            .send(reader);
+
+        ensure_success(response, "write", key)?;
+
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> Result<bool, io::Error> {
+        let response = self.request("HEAD", &self.object_path(key), &[]).call();
+
+        match ensure_success(response, "head", key) {
+            Ok(_) =>
+                Ok(true),
+
+            Err(err) if err.kind() == io::ErrorKind::NotFound =>
+                Ok(false),
+
+            Err(err) =>
+                Err(err),
+        }
+    }
+
+    fn modified(&self, key: &str) -> Result<SystemTime, io::Error> {
+        let response = self.request("HEAD", &self.object_path(key), &[]).call();
+        let response = ensure_success(response, "head", key)?;
+
+        response.header("Last-Modified")
+            .and_then(parse_http_date)
+            .ok_or_else(|| io::Error::other(format!("Failed to head {}: missing last modified", key)))
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>, io::Error> {
+        let objects = self.list_objects(prefix, None)?
+            .iter()
+            .flat_map(|body| xml_elements(body, "Contents"))
+            .filter_map(|contents| {
+                let key = xml_elements(contents, "Key").first().map(|key| xml_unescape(key))?;
+
+                let modified = xml_elements(contents, "LastModified")
+                    .first()
+                    .and_then(|value| parse_timestamp(value))
+                    .unwrap_or_else(SystemTime::now);
+
+                Some(ObjectInfo{
+                    key,
+                    modified,
+                })
+            })
+            .collect();
+
+        Ok(objects)
+    }
+
+    fn list_dirs(&self, prefix: &str) -> Result<Vec<String>, io::Error> {
+        let dirs = self.list_objects(prefix, Some("/"))?
+            .iter()
+            .flat_map(|body| xml_elements(body, "CommonPrefixes"))
+            .filter_map(|common_prefix| xml_elements(common_prefix, "Prefix").first().map(|value| xml_unescape(value)))
+            .filter_map(|dir_prefix| {
+                dir_prefix.get(prefix.len()..)
+                    .map(|name| name.trim_end_matches('/').to_string())
+            })
+            .collect();
+
+        Ok(dirs)
+    }
+
+    fn remove(&self, key: &str) -> Result<(), io::Error> {
+        let response = self.request("DELETE", &self.object_path(key), &[]).call();
+
+        ensure_success(response, "remove", key)?;
+
+        Ok(())
+    }
+
+    fn remove_all(&self, prefix: &str) -> Result<(), io::Error> {
+        self.list(prefix)?
+            .iter()
+            .try_for_each(|object| self.remove(&object.key))
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.temp_dir.clone()
+    }
+
+    fn local_path(&self, _key: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+
+fn object_reader(response: ureq::Response, key: &str) -> Result<ObjectReader, io::Error> {
+    let size = response.header("Content-Length")
+        .and_then(|value| value.parse::<u64>().ok())
+        .ok_or_else(|| io::Error::other(format!("Failed to read {}: missing content length", key)))?;
+
+    Ok(ObjectReader{
+        reader: Box::new(response.into_reader().take(size)),
+        size,
+    })
+}
+
+fn ensure_success(response: ureq::Response, action: &str, key: &str) -> Result<ureq::Response, io::Error> {
+    if let Some(err) = response.synthetic_error() {
+        return Err(io::Error::other(format!("Failed to {} {}: {}", action, key, err)));
+    }
+
+    match response.status() {
+        200..=299 =>
+            Ok(response),
+
+        404 =>
+            Err(io::Error::new(io::ErrorKind::NotFound, format!("Failed to {} {}: not found", action, key))),
+
+        status =>
+            Err(io::Error::other(format!("Failed to {} {}: status {}", action, key, status))),
+    }
+}
+
+fn endpoint_host(endpoint: &str) -> String {
+    endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn canonical_query_string(query: &[(&str, &str)]) -> String {
+    let mut pairs = query
+        .iter()
+        .map(|(key, value)| (uri_encode(key, true), uri_encode(value, true)))
+        .collect::<Vec<(String, String)>>();
+
+    pairs.sort();
+
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+// Percent encodes everything except the unreserved characters, slashes are kept unless encode_slash is set
+fn uri_encode(s: &str, encode_slash: bool) -> String {
+    s.bytes()
+        .map(|byte| {
+            let is_unreserved = byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' || byte == b'.' || byte == b'~';
+
+            if is_unreserved || (byte == b'/' && !encode_slash) {
+                (byte as char).to_string()
+            } else {
+                format!("%{:02X}", byte)
+            }
+        })
+        .collect()
+}
+
+// The headers must be sorted by their lowercase names
+fn canonical_request(method: &str, path: &str, query_string: &str, headers: &[(&str, &str)], payload_hash: &str) -> String {
+    let canonical_headers = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect::<String>();
+
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method, path, query_string, canonical_headers, signed_header_names(headers), payload_hash
+    )
+}
+
+fn signed_header_names(headers: &[(&str, &str)]) -> String {
+    headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>()
+        .join(";")
+}
+
+fn credential_scope(date: &str, region: &str) -> String {
+    format!("{}/{}/s3/aws4_request", date, region)
+}
+
+fn string_to_sign(amz_date: &str, scope: &str, canonical_request: &str) -> String {
+    format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date, scope, sha256_hex(canonical_request.as_bytes())
+    )
+}
+
+fn signature(secret_key: &str, date: &str, region: &str, string_to_sign: &str) -> String {
+    let secret = format!("AWS4{}", secret_key);
+    let date_key = sign(secret.as_bytes(), date.as_bytes());
+    let region_key = sign(&date_key, region.as_bytes());
+    let service_key = sign(&region_key, b"s3");
+    let signing_key = sign(&service_key, b"aws4_request");
+
+    hex::encode(sign(&signing_key, string_to_sign.as_bytes()))
+}
+
+fn sign(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = util::Sha256::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    let timestamp = DateTime::parse_from_rfc3339(value).ok()?.timestamp();
+
+    Some(UNIX_EPOCH + Duration::from_secs(timestamp.max(0) as u64))
+}
+
+// Headers use the http date format, i.e. Wed, 21 Oct 2015 07:28:00 GMT
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let timestamp = DateTime::parse_from_rfc2822(value).ok()?.timestamp();
+
+    Some(UNIX_EPOCH + Duration::from_secs(timestamp.max(0) as u64))
+}
+
+
+// The list responses are small and well known, so the few values that are needed
+// are picked out of the xml instead of pulling in a xml parser
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let start_tag = format!("<{}>", tag);
+    let end_tag = format!("</{}>", tag);
+    let mut elements = vec![];
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&start_tag) {
+        let content_start = start + start_tag.len();
+
+        match rest[content_start..].find(&end_tag) {
+            Some(end) => {
+                elements.push(&rest[content_start..content_start + end]);
+                rest = &rest[content_start + end + end_tag.len()..];
+            },
+
+            None =>
+                break,
+        }
+    }
+
+    elements
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::collections::{BTreeMap, HashMap};
+    use std::thread;
+
+    // The examples from the AWS documentation for signing S3 requests with the Authorization header
+    const SECRET_KEY: &str = "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY";
+    const EMPTY_PAYLOAD_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn sign_example(method: &str, path: &str, query: &[(&str, &str)], headers: &[(&str, &str)]) -> (String, String) {
+        let canonical_request = canonical_request(method, path, &canonical_query_string(query), headers, EMPTY_PAYLOAD_HASH);
+        let scope = credential_scope("20130524", "us-east-1");
+        let string_to_sign = string_to_sign("20130524T000000Z", &scope, &canonical_request);
+
+        (canonical_request, signature(SECRET_KEY, "20130524", "us-east-1", &string_to_sign))
+    }
+
+    #[test]
+    fn parses_http_dates() {
+        let modified = parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT");
+
+        assert_eq!(modified, Some(UNIX_EPOCH + Duration::from_secs(1445412480)));
+        assert_eq!(parse_http_date("2015-10-21T07:28:00Z"), None);
+    }
+
+    #[test]
+    fn signs_get_object() {
+        let headers = [
+            ("host", "examplebucket.s3.amazonaws.com"),
+            ("range", "bytes=0-9"),
+            ("x-amz-content-sha256", EMPTY_PAYLOAD_HASH),
+            ("x-amz-date", "20130524T000000Z"),
+        ];
+
+        let (canonical_request, signature) = sign_example("GET", "/test.txt", &[], &headers);
+
+        assert_eq!(canonical_request, [
+            "GET",
+            "/test.txt",
+            "",
+            "host:examplebucket.s3.amazonaws.com",
+            "range:bytes=0-9",
+            "x-amz-content-sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "x-amz-date:20130524T000000Z",
+            "",
+            "host;range;x-amz-content-sha256;x-amz-date",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ].join("\n"));
+
+        assert_eq!(string_to_sign("20130524T000000Z", &credential_scope("20130524", "us-east-1"), &canonical_request), [
+            "AWS4-HMAC-SHA256",
+            "20130524T000000Z",
+            "20130524/us-east-1/s3/aws4_request",
+            "7344ae5b7ee6c3e7e6b0fe0640412a37625d1fbfff95c48bbb2dc43964946972",
+        ].join("\n"));
+
+        assert_eq!(signature, "f0e8bdb87c964420e857bd35b5d6ed310bd44f0170aba48dd91039c6036bdb41");
+    }
+
+    #[test]
+    fn signs_get_bucket_lifecycle() {
+        let headers = [
+            ("host", "examplebucket.s3.amazonaws.com"),
+            ("x-amz-content-sha256", EMPTY_PAYLOAD_HASH),
+            ("x-amz-date", "20130524T000000Z"),
+        ];
+
+        let (canonical_request, signature) = sign_example("GET", "/", &[("lifecycle", "")], &headers);
+
+        assert!(canonical_request.starts_with("GET\n/\nlifecycle=\nhost:"));
+        assert_eq!(signature, "fea454ca298b7da1c68078a5d1bdbfbbe0d65c699e0f91ac7a200a0136783543");
+    }
+
+    #[test]
+    fn signs_list_objects_with_sorted_query() {
+        let headers = [
+            ("host", "examplebucket.s3.amazonaws.com"),
+            ("x-amz-content-sha256", EMPTY_PAYLOAD_HASH),
+            ("x-amz-date", "20130524T000000Z"),
+        ];
+
+        let (canonical_request, signature) = sign_example("GET", "/", &[("prefix", "J"), ("max-keys", "2")], &headers);
+
+        assert!(canonical_request.starts_with("GET\n/\nmax-keys=2&prefix=J\nhost:"));
+        assert_eq!(signature, "34b48302e7b5fa45bde8084f4b7868a86f0a534bc59db6670ed5711ef69dc6f7");
+    }
+
+    #[test]
+    fn encodes_query_values_and_keeps_slashes_in_paths() {
+        assert_eq!(canonical_query_string(&[("prefix", "sites/a b"), ("list-type", "2")]), "list-type=2&prefix=sites%2Fa%20b");
+        assert_eq!(uri_encode("sites/a b/site.json", false), "sites/a%20b/site.json");
+    }
+
+    // Stand-in for a S3 compatible object store that answers the requests made by S3Storage,
+    // the requests are not authenticated. Every connection is closed after one request
+    fn start_stand_in() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        let addr = listener.local_addr()
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        thread::spawn(move || {
+            let mut objects = BTreeMap::new();
+
+            for stream in listener.incoming().flatten() {
+                let _ = serve(stream, &mut objects);
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn serve(mut stream: TcpStream, objects: &mut BTreeMap<String, Vec<u8>>) -> Result<(), io::Error> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        let mut headers = HashMap::new();
+
+        reader.read_line(&mut request_line)?;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+
+            match line.trim_end().split_once(':') {
+                Some((name, value)) =>
+                    headers.insert(name.to_lowercase(), value.trim().to_string()),
+
+                None =>
+                    break,
+            };
+        }
+
+        let content_length = headers.get("content-length")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        let mut parts = request_line.split(' ');
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next().unwrap_or_default();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let key = uri_decode(path.trim_start_matches("/bucket").trim_start_matches('/'));
+
+        let (status, extra_headers, response_body) = match (method, objects.get(&key)) {
+            ("GET", _) if key.is_empty() =>
+                (200, String::new(), list_response(objects, &query_params(query)).into_bytes()),
+
+            ("PUT", _) => {
+                objects.insert(key, body);
+                (200, String::new(), vec![])
+            },
+
+            ("DELETE", _) => {
+                objects.remove(&key);
+                (204, String::new(), vec![])
+            },
+
+            (_, None) =>
+                (404, String::new(), vec![]),
+
+            ("HEAD", Some(data)) =>
+                (200, "Last-Modified: Wed, 20 May 2020 10:00:00 GMT\r\n".to_string(), vec![0; data.len()]),
+
+            (_, Some(data)) => {
+                let range = headers.get("range")
+                    .and_then(|value| value.trim_start_matches("bytes=").split_once('-'))
+                    .and_then(|(first, last)| Some((first.parse::<usize>().ok()?, last.parse::<usize>().ok()?)));
+
+                match range {
+                    Some((first, last)) =>
+                        (206, String::new(), data[first..=last].to_vec()),
+
+                    None =>
+                        (200, String::new(), data.clone()),
+                }
+            },
+        };
+
+        write!(stream, "HTTP/1.1 {} OK\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n", status, response_body.len(), extra_headers)?;
+
+        if method != "HEAD" {
+            stream.write_all(&response_body)?;
+        }
+
+        stream.flush()
+    }
+
+    fn list_response(objects: &BTreeMap<String, Vec<u8>>, params: &HashMap<String, String>) -> String {
+        let prefix = params.get("prefix").cloned().unwrap_or_default();
+        let mut contents = String::new();
+        let mut common_prefixes = vec![];
+
+        for key in objects.keys().filter(|key| key.starts_with(&prefix)) {
+            let rest = &key[prefix.len()..];
+
+            match rest.find('/').filter(|_| params.contains_key("delimiter")) {
+                Some(end) =>
+                    common_prefixes.push(format!("{}{}", prefix, &rest[..=end])),
+
+                None =>
+                    contents.push_str(&format!("<Contents><Key>{}</Key><LastModified>2020-05-20T10:00:00.000Z</LastModified></Contents>", key)),
+            }
+        }
+
+        common_prefixes.dedup();
+
+        let common_prefixes = common_prefixes
+            .iter()
+            .map(|common_prefix| format!("<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>", common_prefix))
+            .collect::<String>();
+
+        format!("<ListBucketResult><IsTruncated>false</IsTruncated>{}{}</ListBucketResult>", contents, common_prefixes)
+    }
+
+    fn query_params(query: &str) -> HashMap<String, String> {
+        query.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (uri_decode(name), uri_decode(value)))
+            .collect()
+    }
+
+    fn uri_decode(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut decoded = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let escaped = s.get(i + 1..i + 3)
+                .filter(|_| bytes[i] == b'%')
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+            match escaped {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                },
+
+                None => {
+                    decoded.push(bytes[i]);
+                    i += 1;
+                },
+            }
+        }
+
+        String::from_utf8_lossy(&decoded).to_string()
+    }
+
+    fn read_to_string(reader: ObjectReader) -> String {
+        let mut data = String::new();
+
+        reader.reader.take(reader.size).read_to_string(&mut data)
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        data
+    }
+
+    #[test]
+    fn stores_objects_in_a_s3_compatible_store() {
+        let storage = S3Storage::new(Config{
+            endpoint: start_stand_in(),
+            bucket: "bucket".to_string(),
+            region: "us-east-1".to_string(),
+            access_key: "access".to_string(),
+            secret_key: "secret".to_string(),
+        });
+
+        let mut temp_file = NamedTempFile::new()
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        assert!(temp_file.write_all(b"0123456789").is_ok());
+        assert!(storage.write_file("blobs/aa/bb/aabb", temp_file).is_ok());
+        assert!(storage.write("testsite.orri.dev/site.json", b"{}").is_ok());
+        assert!(storage.write("testsite.orri.dev/revisions/1.json", b"{}").is_ok());
+
+        assert_eq!(storage.read("testsite.orri.dev/site.json").ok(), Some(b"{}".to_vec()));
+        assert_eq!(storage.open("blobs/aa/bb/aabb").map(read_to_string).ok(), Some("0123456789".to_string()));
+        assert_eq!(storage.open_range("blobs/aa/bb/aabb", 2, 3).map(read_to_string).ok(), Some("234".to_string()));
+        assert_eq!(storage.open_range("blobs/aa/bb/aabb", 2, 0).map(read_to_string).ok(), Some(String::new()));
+
+        assert!(storage.exists("blobs/aa/bb/aabb").unwrap_or(false));
+        assert_eq!(storage.modified("blobs/aa/bb/aabb").ok(), parse_http_date("Wed, 20 May 2020 10:00:00 GMT"));
+
+        let keys = storage.list("testsite.orri.dev/")
+            .unwrap_or_else(|err| panic!("{}", err))
+            .into_iter()
+            .map(|object| object.key)
+            .collect::<Vec<String>>();
+
+        assert_eq!(keys, vec!["testsite.orri.dev/revisions/1.json", "testsite.orri.dev/site.json"]);
+        assert_eq!(storage.list_dirs("").ok(), Some(vec!["blobs".to_string(), "testsite.orri.dev".to_string()]));
+
+        assert!(storage.remove("blobs/aa/bb/aabb").is_ok());
+        assert!(storage.remove_all("testsite.orri.dev/").is_ok());
+
+        assert!(!storage.exists("blobs/aa/bb/aabb").unwrap_or(true));
+        assert!(storage.list("").map(|objects| objects.is_empty()).unwrap_or(false));
+        assert!(storage.read("testsite.orri.dev/site.json").map_err(|err| err.kind() == io::ErrorKind::NotFound).unwrap_err());
+    }
+}
@@ -25,6 +25,32 @@ impl UrlPath {
     pub fn relative_path(&self) -> PathBuf {
         PathBuf::from(self.0[1..].to_string())
     }
+
+    // The root is not considered to have a trailing slash
+    pub fn has_trailing_slash(&self) -> bool {
+        self.0.len() > 1 && self.0.ends_with('/')
+    }
+
+    pub fn with_trailing_slash(&self) -> UrlPath {
+        if self.0.ends_with('/') {
+            self.clone()
+        } else {
+            UrlPath(format!("{}/", self.0))
+        }
+    }
+
+    pub fn without_trailing_slash(&self) -> UrlPath {
+        if self.has_trailing_slash() {
+            UrlPath(self.0.trim_end_matches('/').to_string())
+        } else {
+            self.clone()
+        }
+    }
+
+    // The index file of the directory, i.e. /docs and /docs/ both give /docs/index.html
+    pub fn index_path(&self) -> UrlPath {
+        UrlPath(format!("{}index.html", self.with_trailing_slash().0))
+    }
 }
 
 
@@ -24,3 +24,20 @@ pub fn sha256(bytes: &[u8]) -> String {
     let digest = digest::digest(&digest::SHA256, bytes);
     hex::encode(digest.as_ref())
 }
+
+
+pub struct Sha256(digest::Context);
+
+impl Sha256 {
+    pub fn new() -> Sha256 {
+        Sha256(digest::Context::new(&digest::SHA256))
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes)
+    }
+
+    pub fn finish(self) -> String {
+        hex::encode(self.0.finish().as_ref())
+    }
+}
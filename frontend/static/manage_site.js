@@ -2,7 +2,24 @@
 
     const elements = {
         removeSiteButton: orri.page.getElement("#remove-site"),
+        revokeSessionsButton: orri.page.getElement("#revoke-sessions"),
         alertError: orri.page.getElement("#alert-error"),
+        settingsForm: orri.page.getElement("#settings-form"),
+        settingsSubmitButton: orri.page.getElement("#settings-submit-button"),
+        rotateKeyForm: orri.page.getElement("#rotate-key-form"),
+        rotateKeySubmitButton: orri.page.getElement("#rotate-key-submit-button"),
+        newSiteKey: orri.page.getElement("#new-site-key"),
+        newSiteKeyValue: orri.page.getElement("#new-site-key-value"),
+        collaboratorForm: orri.page.getElement("#collaborator-form"),
+        collaboratorSubmitButton: orri.page.getElement("#collaborator-submit-button"),
+        newCollaborator: orri.page.getElement("#new-collaborator"),
+        newCollaboratorKey: orri.page.getElement("#new-collaborator-key"),
+        tokenForm: orri.page.getElement("#token-form"),
+        tokenSubmitButton: orri.page.getElement("#token-submit-button"),
+        newToken: orri.page.getElement("#new-token"),
+        newTokenValue: orri.page.getElement("#new-token-value"),
+        addSessionForm: orri.page.getElement("#add-session-form"),
+        addSessionSubmitButton: orri.page.getElement("#add-session-submit-button"),
     };
 
     orri.button.onClick(elements.removeSiteButton, (buttonBodyData, buttonReady) => {
@@ -29,4 +46,212 @@
             .finally(buttonReady);
     });
 
+    orri.button.onClick(elements.revokeSessionsButton, (buttonBodyData, buttonReady) => {
+
+        function reload() {
+            window.location.reload();
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        if (!window.confirm("Do you really want to log out every browser that manages this site, including this one?")) {
+            buttonReady();
+            return;
+        }
+
+        return orri.api.request(elements.revokeSessionsButton.dataset.apiMethod, elements.revokeSessionsButton.dataset.apiUrl, buttonBodyData)
+            .then(orri.api.rejectErrors)
+            .then(reload)
+            .catch(handleError)
+            .finally(buttonReady);
+    });
+
+    document.querySelectorAll(".rollback-revision").forEach(button => {
+        orri.button.onClick(button, (buttonBodyData, buttonReady) => {
+
+            function reload() {
+                window.location.reload();
+            }
+
+            function handleError(err) {
+                orri.page.showError(elements.alertError, err);
+            }
+
+            if (!window.confirm("Do you really want to rollback to this revision?")) {
+                buttonReady();
+                return;
+            }
+
+            return orri.api.request(button.dataset.apiMethod, button.dataset.apiUrl, buttonBodyData)
+                .then(orri.api.rejectErrors)
+                .then(reload)
+                .catch(handleError)
+                .finally(buttonReady);
+        });
+    });
+
+    orri.form.onSubmit(elements.addSessionForm, elements.addSessionSubmitButton, (formData, formReady) => {
+
+        function reload() {
+            window.location.reload();
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        return orri.api.request(elements.addSessionForm.dataset.apiMethod, elements.addSessionForm.dataset.apiUrl, formData)
+            .then(orri.api.rejectErrors)
+            .then(reload)
+            .catch(handleError)
+            .finally(formReady);
+    });
+
+    orri.form.onSubmit(elements.settingsForm, elements.settingsSubmitButton, (formData, formReady) => {
+
+        function reload() {
+            window.location.reload();
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        return orri.api.request(elements.settingsForm.dataset.apiMethod, elements.settingsForm.dataset.apiUrl, formData)
+            .then(orri.api.rejectErrors)
+            .then(reload)
+            .catch(handleError)
+            .finally(formReady);
+    });
+
+    orri.form.onSubmit(elements.rotateKeyForm, elements.rotateKeySubmitButton, (formData, formReady) => {
+
+        const newKey = orri.crypto.randomString(20);
+
+        function showKey() {
+            elements.newSiteKeyValue.value = newKey;
+            orri.page.unhideElement(elements.newSiteKey);
+            elements.rotateKeyForm.reset();
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        if (!window.confirm("Do you really want to replace the site key? All API tokens and sessions, including this one, will stop working.")) {
+            formReady();
+            return;
+        }
+
+        const data = {
+            key: formData.key,
+            newKey: newKey,
+        };
+
+        return orri.api.request(elements.rotateKeyForm.dataset.apiMethod, elements.rotateKeyForm.dataset.apiUrl, data)
+            .then(orri.api.rejectErrors)
+            .then(showKey)
+            .catch(handleError)
+            .finally(formReady);
+    });
+
+    document.querySelectorAll(".remove-collaborator").forEach(button => {
+        orri.button.onClick(button, (buttonBodyData, buttonReady) => {
+
+            function reload() {
+                window.location.reload();
+            }
+
+            function handleError(err) {
+                orri.page.showError(elements.alertError, err);
+            }
+
+            if (!window.confirm("Do you really want to revoke the access of this collaborator?")) {
+                buttonReady();
+                return;
+            }
+
+            return orri.api.request(button.dataset.apiMethod, button.dataset.apiUrl, buttonBodyData)
+                .then(orri.api.rejectErrors)
+                .then(reload)
+                .catch(handleError)
+                .finally(buttonReady);
+        });
+    });
+
+    orri.form.onSubmit(elements.collaboratorForm, elements.collaboratorSubmitButton, (formData, formReady) => {
+
+        function showKey(res) {
+            return res.json().then(data => {
+                elements.newCollaboratorKey.value = data.key;
+                orri.page.unhideElement(elements.newCollaborator);
+                elements.collaboratorForm.reset();
+            });
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        return orri.api.request(elements.collaboratorForm.dataset.apiMethod, elements.collaboratorForm.dataset.apiUrl, formData)
+            .then(orri.api.rejectErrors)
+            .then(showKey)
+            .catch(handleError)
+            .finally(formReady);
+    });
+
+    document.querySelectorAll(".revoke-token").forEach(button => {
+        orri.button.onClick(button, (buttonBodyData, buttonReady) => {
+
+            function reload() {
+                window.location.reload();
+            }
+
+            function handleError(err) {
+                orri.page.showError(elements.alertError, err);
+            }
+
+            if (!window.confirm("Do you really want to revoke this token?")) {
+                buttonReady();
+                return;
+            }
+
+            return orri.api.request(button.dataset.apiMethod, button.dataset.apiUrl, buttonBodyData)
+                .then(orri.api.rejectErrors)
+                .then(reload)
+                .catch(handleError)
+                .finally(buttonReady);
+        });
+    });
+
+    orri.form.onSubmit(elements.tokenForm, elements.tokenSubmitButton, (formData, formReady) => {
+
+        function showToken(res) {
+            return res.json().then(data => {
+                elements.newTokenValue.value = data.token;
+                orri.page.unhideElement(elements.newToken);
+                elements.tokenForm.reset();
+            });
+        }
+
+        function handleError(err) {
+            orri.page.showError(elements.alertError, err);
+        }
+
+        const data = {
+            name: formData.name,
+            scope: formData.scope,
+            pathPrefix: formData.pathPrefix,
+            expiresInDays: formData.expiresInDays ? parseInt(formData.expiresInDays, 10) : null,
+        };
+
+        return orri.api.request(elements.tokenForm.dataset.apiMethod, elements.tokenForm.dataset.apiUrl, data)
+            .then(orri.api.rejectErrors)
+            .then(showToken)
+            .catch(handleError)
+            .finally(formReady);
+    });
+
 })();
@@ -122,8 +122,17 @@ function File() {
         return promise;
     }
 
+    function getFile(elem) {
+        if (!elem.files || elem.files.length === 0) {
+            return Promise.reject("No file selected");
+        }
+
+        return Promise.resolve(elem.files[0]);
+    }
+
     return {
         onLoad: onLoad,
+        getFile: getFile,
     };
 }
 
@@ -138,6 +147,14 @@ function Api() {
         });
     }
 
+    function upload(method, url, body, headers) {
+        return fetch(url, {
+            method: method,
+            headers: headers,
+            body: body,
+        });
+    }
+
     function rejectErrors(res) {
         if (res.ok) {
             return res;
@@ -148,6 +165,7 @@ function Api() {
 
     return {
         request: request,
+        upload: upload,
         rejectErrors: rejectErrors,
     };
 }
@@ -5,21 +5,78 @@
         alertError: orri.page.getElement("#alert-error"),
         submitButton: orri.page.getElement("#submit-button"),
         file: orri.page.getElement("#file"),
+        kind: orri.page.getElement("#kind"),
+        fileGroup: orri.page.getElement("#file-group"),
+        redirectGroup: orri.page.getElement("#redirect-group"),
+        target: orri.page.getElement("#target"),
     };
 
+    function showKindFields() {
+        const isRedirect = elements.kind.value === "redirect";
+
+        if (isRedirect) {
+            orri.page.hideElement(elements.fileGroup);
+            orri.page.unhideElement(elements.redirectGroup);
+        } else {
+            orri.page.unhideElement(elements.fileGroup);
+            orri.page.hideElement(elements.redirectGroup);
+        }
+
+        elements.file.required = !isRedirect;
+        elements.target.required = isRedirect;
+    }
+
+    elements.kind.addEventListener("change", showKindFields);
+    showKindFields();
+
     orri.form.onSubmit(elements.form, elements.submitButton, (formData, formReady) => {
 
-        function prepareData(file) {
-            return {
-                domain: formData.domain,
+        function multipartUpload(file) {
+            const body = new FormData();
+
+            // The path and key must be sent before the file
+            body.append("path", formData.path);
+
+            if (formData.key) {
+                body.append("key", formData.key);
+            }
+
+            body.append("file", file);
+
+            return orri.api.upload(elements.form.dataset.apiMethod, elements.form.dataset.apiUrl, body, {});
+        }
+
+        function rawUpload(file) {
+            const url = elements.form.dataset.apiUrl + "?path=" + encodeURIComponent(formData.path);
+            const headers = {};
+
+            if (formData.key) {
+                headers["X-Site-Key"] = formData.key;
+            }
+
+            return orri.api.upload(elements.form.dataset.apiMethod, url, file, headers);
+        }
+
+        function uploadFile(file) {
+            const upload = elements.form.dataset.apiEncoding === "multipart" ? multipartUpload : rawUpload;
+
+            return upload(file)
+                .then(orri.api.rejectErrors)
+                .then(res => res.json());
+        }
+
+        function saveRedirect() {
+            const body = {
                 path: formData.path,
-                dataUrl: file.dataUrl,
-                key: formData.key,
+                target: formData.target,
+                status: parseInt(formData.status, 10),
             };
-        }
 
-        function updateRoute(data) {
-            return orri.api.request(elements.form.dataset.apiMethod, elements.form.dataset.apiUrl, data)
+            if (formData.key) {
+                body.key = formData.key;
+            }
+
+            return orri.api.request(elements.form.dataset.redirectApiMethod, elements.form.dataset.redirectApiUrl, body)
                 .then(orri.api.rejectErrors)
                 .then(res => res.json());
         }
@@ -32,9 +89,15 @@
             orri.page.showError(elements.alertError, err);
         }
 
-        orri.file.onLoad(elements.file)
-            .then(prepareData)
-            .then(updateRoute)
+        if (formData.kind === "redirect") {
+            return saveRedirect()
+                .then(redirect)
+                .catch(handleError)
+                .finally(formReady);
+        }
+
+        orri.file.getFile(elements.file)
+            .then(uploadFile)
             .then(redirect)
             .catch(handleError)
             .catch(handleError)